@@ -0,0 +1,54 @@
+/// Converts a Rust type or variant name to snake case, keeping the acronyms together.
+///
+/// For instance, `OrderPlaced` becomes `order_placed` and `HTTPRequest` becomes `http_request`.
+pub(crate) fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake = String::with_capacity(name.len() + 4);
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+
+            if prev != '_' && (prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_is_lower)) {
+                snake.push('_');
+            }
+        }
+
+        snake.extend(c.to_lowercase());
+    }
+
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_snake_case;
+
+    #[test]
+    fn converts_camel_case_words() {
+        assert_eq!(to_snake_case("OrderPlaced"), "order_placed");
+        assert_eq!(to_snake_case("Placed"), "placed");
+    }
+
+    #[test]
+    fn keeps_acronyms_together() {
+        assert_eq!(to_snake_case("HTTPRequest"), "http_request");
+        assert_eq!(to_snake_case("IOError"), "io_error");
+        assert_eq!(to_snake_case("UserIO"), "user_io");
+        assert_eq!(to_snake_case("ABC"), "abc");
+    }
+
+    #[test]
+    fn separates_words_after_digits() {
+        assert_eq!(to_snake_case("V2Ready"), "v2_ready");
+        assert_eq!(to_snake_case("HTTP2Client"), "http2_client");
+        assert_eq!(to_snake_case("Order66"), "order66");
+    }
+
+    #[test]
+    fn keeps_existing_underscores() {
+        assert_eq!(to_snake_case("Already_Snake"), "already_snake");
+        assert_eq!(to_snake_case("snake_case"), "snake_case");
+    }
+}
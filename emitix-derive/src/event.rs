@@ -0,0 +1,170 @@
+use super::case::to_snake_case;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Attribute, Data, DeriveInput, Error, Fields, Ident, LitStr, Result, Type, Variant};
+
+/// The code generated for one variant of the event enum.
+struct EventVariant {
+    ident:    Ident,
+    kind:     String,
+    constant: Ident,
+    helpers:  TokenStream,
+}
+
+pub(crate) fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`Event` can only be derived for enums",
+        ));
+    };
+
+    if data.variants.is_empty() {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`Event` can't be derived for an enum without variants",
+        ));
+    }
+
+    let namespace = match parse_attribute(&input.attrs, "namespace")? {
+        Some(namespace) => namespace.value(),
+        None => to_snake_case(&input.ident.to_string()),
+    };
+
+    let mut variants = Vec::<EventVariant>::with_capacity(data.variants.len());
+    for variant in &data.variants {
+        let event_variant = derive_variant(variant, &namespace, &input)?;
+
+        if let Some(other) = variants.iter().find(|other| other.kind == event_variant.kind) {
+            let message = format!("The event kind `{}` is already used by `{}`", other.kind, other.ident);
+
+            return Err(Error::new_spanned(&variant.ident, message));
+        }
+
+        variants.push(event_variant);
+    }
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let idents = variants.iter().map(|variant| &variant.ident);
+    let kinds = variants.iter().map(|variant| &variant.kind).collect::<Vec<_>>();
+    let constants = variants.iter().map(|variant| &variant.constant).collect::<Vec<_>>();
+    let helpers = variants.iter().map(|variant| &variant.helpers);
+    let constant_docs = variants
+        .iter()
+        .map(|variant| format!("The event kind of `{name}::{}`.", variant.ident));
+
+    Ok(quote! {
+        impl #impl_generics ::emitix::Event for #name #ty_generics #where_clause {
+            const KINDS: &'static [&'static str] = &[#(#kinds),*];
+
+            fn kind(&self) -> &'static str {
+                match self {
+                    #(Self::#idents { .. } => Self::#constants,)*
+                }
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(
+                #[doc = #constant_docs]
+                #vis const #constants: &'static str = #kinds;
+            )*
+
+            #(#helpers)*
+        }
+    })
+}
+
+fn derive_variant(variant: &Variant, namespace: &str, input: &DeriveInput) -> Result<EventVariant> {
+    let ident = &variant.ident;
+    let snake_name = to_snake_case(&ident.to_string());
+    let kind = match parse_attribute(&variant.attrs, "kind")? {
+        Some(kind) => kind.value(),
+        None => format!("{namespace}.{snake_name}"),
+    };
+
+    let constant = format_ident!("{}_KIND", snake_name.to_uppercase());
+    let on_fn = format_ident!("on_{}", snake_name);
+    let emit_fn = format_ident!("emit_{}", snake_name);
+
+    let (bindings, types): (Vec<Ident>, Vec<&Type>) = match &variant.fields {
+        Fields::Unit => (vec![], vec![]),
+        Fields::Unnamed(fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, field)| (format_ident!("field_{}", i), &field.ty))
+            .unzip(),
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|field| (field.ident.clone().expect("Named fields have an identifier"), &field.ty))
+            .unzip(),
+    };
+
+    let pattern = match &variant.fields {
+        Fields::Unit => quote!(Self::#ident),
+        Fields::Unnamed(_) => quote!(Self::#ident(#(#bindings),*)),
+        Fields::Named(_) => quote!(Self::#ident { #(#bindings),* }),
+    };
+
+    let (listener_bound, listener_call) = match types.as_slice() {
+        [] => (quote!(FnMut()), quote!(listener())),
+        [ty] => (quote!(FnMut(#ty)), quote!(listener(#(#bindings)*))),
+        _ => (quote!(FnMut((#(#types),*))), quote!(listener((#(#bindings),*)))),
+    };
+
+    let vis = &input.vis;
+    let on_doc = format!("Adds a listener for the events `{}::{ident}` to a hub.", input.ident);
+    let emit_doc = format!("Emits the event `{}::{ident}` built from its fields.", input.ident);
+    let helpers = quote! {
+        #[doc = #on_doc]
+        #[allow(irrefutable_let_patterns)]
+        #vis fn #on_fn<F>(
+            hub: &::emitix::event_hub::EventHub<Self>,
+            mut listener: F,
+        ) -> ::emitix::Result<::emitix::__private::Uuid>
+        where
+            F: #listener_bound + Send + Sync + 'static,
+        {
+            ::emitix::EventManager::add_listener(hub, Self::#constant, move |event: Self| {
+                if let #pattern = event {
+                    #listener_call;
+                }
+            })
+        }
+
+        #[doc = #emit_doc]
+        #vis fn #emit_fn(hub: &::emitix::event_hub::EventHub<Self>, #(#bindings: #types),*) -> ::emitix::Result<()> {
+            hub.emit(Self::#constant, #pattern)
+        }
+    };
+
+    Ok(EventVariant {
+        ident: ident.clone(),
+        kind,
+        constant,
+        helpers,
+    })
+}
+
+/// Parses the value of a key in the `#[event(...)]` attributes, rejecting the other keys.
+fn parse_attribute(attrs: &[Attribute], key: &str) -> Result<Option<LitStr>> {
+    let mut value = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("event")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(key) {
+                value = Some(meta.value()?.parse()?);
+
+                Ok(())
+            } else {
+                Err(meta.error(format!("Unsupported event attribute, expected `{key}`")))
+            }
+        })?;
+    }
+
+    Ok(value)
+}
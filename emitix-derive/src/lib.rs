@@ -0,0 +1,127 @@
+//! `emitix-derive` - The derive macros of the `emitix` event management library
+//!
+//! The macros are re-exported by `emitix` when its `derive` feature is enabled.
+mod case;
+mod event;
+mod listeners;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, Error, ItemImpl};
+
+/// Derives the `Event` trait for an enum whose variants are event kinds.
+///
+/// Each variant gets a stable event kind, which is the snake case name of the enum
+/// followed by a dot and the snake case name of the variant, like `order_event.placed`.
+/// The prefix can be replaced with `#[event(namespace = "...")]` on the enum,
+/// and the whole event kind with `#[event(kind = "...")]` on a variant.
+///
+/// For each variant, the macro generates in the enum:
+/// - a constant with the event kind of the variant, like `PLACED_KIND`,
+/// - an `on_<variant>` function adding a listener for the variant to an `EventHub`,
+///   which is called with the fields of the variant: the field itself when there is one, a tuple otherwise,
+/// - an `emit_<variant>` function building the variant from its fields and emitting it.
+///
+/// The enum must implement `Clone`, `Send` and `Sync`.
+///
+/// # Example
+/// ```rust
+/// use emitix::{event_hub::EventHub, Event};
+///
+/// #[derive(Clone, Event)]
+/// #[event(namespace = "orders")]
+/// enum OrderEvent {
+///     Placed(u32),
+///     Shipped {
+///         id:      u32,
+///         carrier: String,
+///     },
+///     #[event(kind = "orders.closed")]
+///     Cancelled,
+/// }
+///
+/// let hub = EventHub::default();
+/// OrderEvent::on_shipped(&hub, |(id, carrier)| {
+///     println!("Order {id} shipped by {carrier}")
+/// })
+/// .unwrap();
+/// OrderEvent::on_cancelled(&hub, || println!("Order cancelled")).unwrap();
+///
+/// OrderEvent::emit_shipped(&hub, 42, String::from("Parcel Express")).unwrap();
+/// hub.emit_event(OrderEvent::Cancelled).unwrap();
+///
+/// assert_eq!(OrderEvent::PLACED_KIND, "orders.placed");
+/// assert_eq!(OrderEvent::Cancelled.kind(), "orders.closed");
+/// ```
+#[proc_macro_derive(Event, attributes(event))]
+pub fn derive_event(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    event::derive(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+/// Registers the methods of an impl block as event listeners.
+///
+/// Each method annotated with `#[on("...")]` is a listener for the given event kind or pattern.
+/// It takes `&self` and the event, and returns nothing or a `Result` whose error is reported by the emission.
+/// A method can be annotated several times to listen for several event kinds.
+///
+/// The macro adds to the impl block a `register(self: Arc<Self>, hub: &impl EventManager<T>)` method,
+/// which adds all the listeners to an event manager and returns their `Subscriptions`.
+/// The listeners are removed together when the subscriptions are dropped.
+///
+/// All the listener methods must take the same event type, and the type must be `Send`, `Sync` and `'static`.
+///
+/// # Example
+/// ```rust
+/// use emitix::{event_hub::EventHub, EventManager};
+/// use std::sync::{
+///     atomic::{AtomicUsize, Ordering},
+///     Arc,
+/// };
+///
+/// #[derive(Default)]
+/// struct AuditService {
+///     entries: AtomicUsize,
+/// }
+///
+/// #[emitix::listeners]
+/// impl AuditService {
+///     #[on("user.created")]
+///     #[on("user.deleted")]
+///     fn record(&self, name: String) {
+///         println!("Audit: {name}");
+///         self.entries.fetch_add(1, Ordering::SeqCst);
+///     }
+///
+///     #[on("user.renamed")]
+///     fn check_name(&self, name: String) -> anyhow::Result<()> {
+///         anyhow::ensure!(!name.is_empty(), "Empty user name");
+///
+///         Ok(())
+///     }
+/// }
+///
+/// let hub = EventHub::default();
+/// let service = Arc::new(AuditService::default());
+/// let subscriptions = service.clone().register(&hub).unwrap();
+///
+/// hub.emit("user.created", String::from("Alice")).unwrap();
+/// hub.emit("user.deleted", String::from("Bob")).unwrap();
+/// assert!(hub.emit("user.renamed", String::new()).is_err());
+/// assert_eq!(service.entries.load(Ordering::SeqCst), 2);
+///
+/// drop(subscriptions);
+/// assert!(!hub.has_listeners("user.created").unwrap());
+/// ```
+#[proc_macro_attribute]
+pub fn listeners(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return Error::new(proc_macro2::Span::call_site(), "`listeners` doesn't take any argument")
+            .into_compile_error()
+            .into();
+    }
+
+    let item = parse_macro_input!(item as ItemImpl);
+
+    listeners::expand(item).unwrap_or_else(Error::into_compile_error).into()
+}
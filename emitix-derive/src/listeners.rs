@@ -0,0 +1,168 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Error, FnArg, Ident, ImplItem, ImplItemFn, ItemImpl, LitStr, Result, ReturnType, Type};
+
+/// A method of the impl block registered as a listener.
+struct ListenerMethod {
+    ident:    Ident,
+    kinds:    Vec<LitStr>,
+    event:    Type,
+    fallible: bool,
+}
+
+pub(crate) fn expand(mut item: ItemImpl) -> Result<TokenStream> {
+    if let Some((_, path, _)) = &item.trait_ {
+        return Err(Error::new_spanned(
+            path,
+            "`listeners` can only be used on an inherent impl block",
+        ));
+    }
+
+    let mut methods = Vec::<ListenerMethod>::new();
+    for impl_item in &mut item.items {
+        if let ImplItem::Fn(method) = impl_item
+            && let Some(listener) = listener_method(method)?
+        {
+            methods.push(listener);
+        }
+    }
+
+    let Some(first) = methods.first() else {
+        return Err(Error::new_spanned(
+            &item.self_ty,
+            "`listeners` expects at least one method annotated with `#[on(\"...\")]`",
+        ));
+    };
+
+    let event = &first.event;
+    let event_tokens = event.to_token_stream().to_string();
+    if let Some(other) = methods
+        .iter()
+        .find(|method| method.event.to_token_stream().to_string() != event_tokens)
+    {
+        let message = format!("All the listeners must take the same event type as `{}`", first.ident);
+
+        return Err(Error::new_spanned(&other.event, message));
+    }
+
+    let registrations = methods.iter().flat_map(|method| {
+        let ident = &method.ident;
+
+        method.kinds.iter().map(move |kind| {
+            if method.fallible {
+                quote! {
+                    let this = ::std::sync::Arc::clone(&self);
+                    subscriptions.push(::emitix::EventManager::add_scoped_fallible_listener(
+                        hub,
+                        #kind,
+                        move |event: #event| {
+                            this.#ident(event)?;
+
+                            Ok(())
+                        },
+                    )?);
+                }
+            } else {
+                quote! {
+                    let this = ::std::sync::Arc::clone(&self);
+                    subscriptions.push(::emitix::EventManager::add_scoped_listener(
+                        hub,
+                        #kind,
+                        move |event: #event| this.#ident(event),
+                    )?);
+                }
+            }
+        })
+    });
+
+    let register = quote! {
+        /// Registers the listener methods on an event manager.
+        ///
+        /// The listeners are removed together when the returned subscriptions are dropped.
+        pub fn register<M: ::emitix::EventManager<#event>>(
+            self: ::std::sync::Arc<Self>,
+            hub: &M,
+        ) -> ::emitix::Result<::emitix::Subscriptions> {
+            let mut subscriptions = ::emitix::Subscriptions::new();
+
+            #(#registrations)*
+
+            Ok(subscriptions)
+        }
+    };
+
+    item.items.push(ImplItem::Verbatim(register));
+
+    Ok(item.into_token_stream())
+}
+
+/// Takes the `#[on("...")]` attributes off a method, and checks its signature when it has some.
+fn listener_method(method: &mut ImplItemFn) -> Result<Option<ListenerMethod>> {
+    let mut kinds = vec![];
+    let mut attrs = Vec::with_capacity(method.attrs.len());
+
+    for attr in method.attrs.drain(..) {
+        if attr.path().is_ident("on") {
+            kinds.push(attr.parse_args::<LitStr>()?);
+        } else {
+            attrs.push(attr);
+        }
+    }
+
+    method.attrs = attrs;
+    if kinds.is_empty() {
+        return Ok(None);
+    }
+
+    let sig = &method.sig;
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(Error::new_spanned(asyncness, "A listener method can't be async"));
+    }
+
+    let event = match sig.inputs.iter().collect::<Vec<_>>().as_slice() {
+        [FnArg::Receiver(receiver), FnArg::Typed(event)]
+            if receiver.reference.is_some() && receiver.mutability.is_none() =>
+        {
+            (*event.ty).clone()
+        }
+        _ => {
+            return Err(Error::new_spanned(
+                &sig.inputs,
+                "A listener method must take `&self` and the event",
+            ));
+        }
+    };
+
+    let fallible = match &sig.output {
+        ReturnType::Default => false,
+        ReturnType::Type(_, output) if is_result(output) => true,
+        ReturnType::Type(_, output) => {
+            return Err(Error::new_spanned(
+                output,
+                "A listener method must return nothing or a `Result`",
+            ));
+        }
+    };
+
+    Ok(Some(ListenerMethod {
+        ident: sig.ident.clone(),
+        kinds,
+        event,
+        fallible,
+    }))
+}
+
+/// Checks if a return type is a `Result`, like `anyhow::Result<()>` or `Result<(), MyError>`.
+fn is_result(output: &Type) -> bool {
+    match output {
+        Type::Path(path) => {
+            path.qself.is_none()
+                && path
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "Result")
+        }
+        _ => false,
+    }
+}
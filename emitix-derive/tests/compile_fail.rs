@@ -0,0 +1,6 @@
+#[test]
+fn compile_fail() {
+    let cases = trybuild::TestCases::new();
+
+    cases.compile_fail("tests/ui/*.rs");
+}
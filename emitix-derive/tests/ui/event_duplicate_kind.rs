@@ -0,0 +1,10 @@
+use emitix::Event;
+
+#[derive(Clone, Event)]
+enum OrderEvent {
+    Placed,
+    #[event(kind = "order_event.placed")]
+    Created,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use emitix::Event;
+
+#[derive(Clone, Event)]
+struct OrderPlaced {
+    id: u32,
+}
+
+fn main() {}
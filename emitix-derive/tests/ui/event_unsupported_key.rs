@@ -0,0 +1,9 @@
+use emitix::Event;
+
+#[derive(Clone, Event)]
+#[event(name = "orders")]
+enum OrderEvent {
+    Placed,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use emitix::Event;
+
+#[derive(Clone, Event)]
+enum OrderEvent {
+    #[event(namespace = "orders")]
+    Placed,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+struct AuditService;
+
+#[emitix::listeners]
+impl AuditService {
+    #[on("user.created")]
+    async fn record(&self, name: String) {
+        println!("Audit: {name}");
+    }
+}
+
+fn main() {}
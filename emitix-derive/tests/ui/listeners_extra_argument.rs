@@ -0,0 +1,11 @@
+struct AuditService;
+
+#[emitix::listeners]
+impl AuditService {
+    #[on("user.created")]
+    fn record(&self, name: String, level: u8) {
+        println!("Audit {level}: {name}");
+    }
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+struct AuditService;
+
+#[emitix::listeners]
+impl AuditService {
+    #[on("user.created")]
+    fn record(&self, name: String) {
+        println!("Audit: {name}");
+    }
+
+    #[on("order.placed")]
+    fn count(&self, id: u32) {
+        println!("Order: {id}");
+    }
+}
+
+fn main() {}
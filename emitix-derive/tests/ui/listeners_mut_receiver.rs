@@ -0,0 +1,13 @@
+struct AuditService {
+    entries: usize,
+}
+
+#[emitix::listeners]
+impl AuditService {
+    #[on("user.created")]
+    fn record(&mut self, _name: String) {
+        self.entries += 1;
+    }
+}
+
+fn main() {}
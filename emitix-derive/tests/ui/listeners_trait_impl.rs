@@ -0,0 +1,15 @@
+trait Audit {
+    fn record(&self, name: String);
+}
+
+struct AuditService;
+
+#[emitix::listeners]
+impl Audit for AuditService {
+    #[on("user.created")]
+    fn record(&self, name: String) {
+        println!("Audit: {name}");
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+struct AuditService;
+
+#[emitix::listeners]
+impl AuditService {
+    #[on("user.created")]
+    fn record(&self, name: String) -> bool {
+        !name.is_empty()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+struct AuditService;
+
+#[emitix::listeners]
+impl AuditService {
+    fn record(&self, name: String) {
+        println!("Audit: {name}");
+    }
+}
+
+fn main() {}
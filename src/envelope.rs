@@ -0,0 +1,135 @@
+use uuid::Uuid;
+use std::time::SystemTime;
+
+/// The metadata stamped on each emission of a hub in envelope mode.
+///
+/// It's given to the contextual listeners by [`EventContext::metadata`](crate::event_hub::EventContext::metadata),
+/// and to the envelope listeners within their [`Envelope`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventMetadata {
+    /// The identifier of the emission.
+    pub id:                Uuid,
+    /// The position of the emission among the emissions of the hub, starting at `0`,
+    /// or `None` when the event was not emitted by a hub in envelope mode.
+    pub seq:               Option<u64>,
+    /// The time when the event was emitted.
+    pub timestamp:         SystemTime,
+    /// The identifier of the emitter which emitted the event, if it was emitted by an emitter.
+    pub source_emitter_id: Option<Uuid>,
+    /// The identifier shared by an event and all the events it caused, directly or not.
+    pub correlation_id:    Uuid,
+    /// The identifier of the emission whose listener emitted the event, if any.
+    pub causation_id:      Option<Uuid>,
+}
+
+impl EventMetadata {
+    /// Creates the metadata of an event which was not caused by another event, and has no sequence number.
+    pub(crate) fn root() -> Self {
+        let id = Uuid::new_v4();
+
+        Self {
+            id,
+            seq: None,
+            timestamp: SystemTime::now(),
+            source_emitter_id: None,
+            correlation_id: id,
+            causation_id: None,
+        }
+    }
+}
+
+/// An event with its event kind and its metadata, for sending the traffic of a hub to logs, files
+/// or other processes, and for the listeners needing to know when and why an event was emitted.
+///
+/// It's received by the listeners added with
+/// [`EventHub::add_envelope_listener`](crate::event_hub::EventHub::add_envelope_listener).
+/// With the `serde` feature, the envelope can be serialized when the event kind and the payload can,
+/// and an envelope received from elsewhere can be emitted with
+/// [`EventHub::emit_envelope`](crate::event_hub::EventHub::emit_envelope).
+///
+/// # Example
+/// ```rust
+/// use emitix::{event_hub::EventHub, Envelope, EventManager};
+///
+/// let manager = EventHub::default();
+/// manager
+///     .add_listener("user.created", |name: String| {
+///         println!("User created: {name}")
+///     })
+///     .unwrap();
+///
+/// let envelope = Envelope::new(String::from("user.created"), String::from("Alice"));
+/// manager.emit_envelope(envelope).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Envelope<T, K = String> {
+    /// The identifier of the emission.
+    pub id:                Uuid,
+    /// The position of the emission among the emissions of the hub, starting at `0`,
+    /// or `None` when the event was not emitted by a hub in envelope mode.
+    pub seq:               Option<u64>,
+    /// The time when the event was emitted.
+    pub timestamp:         SystemTime,
+    /// The event kind the event is emitted for.
+    pub kind:              K,
+    /// The identifier of the emitter which emitted the event, if it was emitted by an emitter.
+    pub source_emitter_id: Option<Uuid>,
+    /// The identifier shared by an event and all the events it caused, directly or not.
+    pub correlation_id:    Uuid,
+    /// The identifier of the emission whose listener emitted the event, if any.
+    pub causation_id:      Option<Uuid>,
+    /// The event argument passed to the listeners.
+    pub payload:           T,
+}
+
+impl<T, K> Envelope<T, K> {
+    /// Creates an envelope for an event which was not caused by another event.
+    ///
+    /// The envelope gets a new identifier, which is also its correlation identifier, and the current time.
+    /// It has no sequence number, which is assigned when a hub in envelope mode emits it.
+    ///
+    /// # Arguments
+    /// - `kind`: The event kind the event is emitted for.
+    /// - `payload`: The event argument passed to the listeners.
+    pub fn new(kind: K, payload: T) -> Self {
+        Self::with_metadata(EventMetadata::root(), kind, payload)
+    }
+
+    /// Creates an envelope for an event with its metadata.
+    ///
+    /// # Arguments
+    /// - `metadata`: The metadata of the emission of the event.
+    /// - `kind`: The event kind the event is emitted for.
+    /// - `payload`: The event argument passed to the listeners.
+    pub fn with_metadata(metadata: EventMetadata, kind: K, payload: T) -> Self {
+        Self {
+            id: metadata.id,
+            seq: metadata.seq,
+            timestamp: metadata.timestamp,
+            kind,
+            source_emitter_id: metadata.source_emitter_id,
+            correlation_id: metadata.correlation_id,
+            causation_id: metadata.causation_id,
+            payload,
+        }
+    }
+
+    /// Returns the metadata of the envelope.
+    pub fn metadata(&self) -> EventMetadata {
+        EventMetadata {
+            id:                self.id,
+            seq:               self.seq,
+            timestamp:         self.timestamp,
+            source_emitter_id: self.source_emitter_id,
+            correlation_id:    self.correlation_id,
+            causation_id:      self.causation_id,
+        }
+    }
+
+    /// Returns the event kind and the payload of the envelope.
+    pub fn into_parts(self) -> (K, T) {
+        (self.kind, self.payload)
+    }
+}
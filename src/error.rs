@@ -0,0 +1,275 @@
+use uuid::Uuid;
+use std::{any::Any, error::Error, fmt};
+
+/// The result type returned by the event managers and the event emitters.
+pub type Result<T, E = EmitixError> = std::result::Result<T, E>;
+
+/// The failure of one listener while an event was emitted.
+#[derive(Debug)]
+pub struct ListenerFailure {
+    /// The identifier of the failing listener.
+    pub id:     Uuid,
+    /// The event kind the failing listener is registered for.
+    pub kind:   String,
+    /// The error returned by the listener.
+    pub source: anyhow::Error,
+}
+
+impl ListenerFailure {
+    /// Checks if the failure comes from a panicking listener.
+    pub fn is_panic(&self) -> bool {
+        self.source.is::<ListenerPanic>()
+    }
+
+    /// Returns a report of the failure, which can be cloned and serialized unlike the failure.
+    pub fn report(&self) -> FailureReport {
+        FailureReport {
+            id:       self.id,
+            kind:     self.kind.clone(),
+            message:  format!("{:#}", self.source),
+            panicked: self.is_panic(),
+        }
+    }
+}
+
+impl fmt::Display for ListenerFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Listener `{}` failed for kind `{}`: {}",
+            self.id, self.kind, self.source
+        )
+    }
+}
+
+impl Error for ListenerFailure {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// The error reported for a listener which panicked while the panics were caught.
+#[derive(Debug, Clone)]
+pub struct ListenerPanic {
+    /// The panic message, when the panic payload is a string.
+    pub message: String,
+}
+
+impl ListenerPanic {
+    pub(crate) fn from_payload(payload: Box<dyn Any + Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&'static str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => String::from("Box<dyn Any>"),
+            },
+        };
+
+        Self {
+            message,
+        }
+    }
+}
+
+impl fmt::Display for ListenerPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Listener panicked: {}", self.message)
+    }
+}
+
+impl Error for ListenerPanic {}
+
+/// The error reported for an asynchronous listener reached by a synchronous emission.
+///
+/// The asynchronous listeners are not called by the synchronous emissions, which would have to block
+/// the emitting thread on their futures. They are only called by
+/// [`EventHub::emit_async`](crate::event_hub::EventHub::emit_async).
+///
+/// # Example
+/// ```rust
+/// use emitix::{event_hub::EventHub, AsyncListenerSkipped};
+///
+/// let manager = EventHub::default();
+/// manager
+///     .add_async_listener("Events You Like", |_: String| async { Ok(()) })
+///     .unwrap();
+///
+/// let err = manager
+///     .emit("Events You Like", String::from("Test Event"))
+///     .unwrap_err();
+///
+/// assert!(err.failures()[0].source.is::<AsyncListenerSkipped>());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AsyncListenerSkipped;
+
+impl fmt::Display for AsyncListenerSkipped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Asynchronous listener skipped by a synchronous emission, use `emit_async` instead")
+    }
+}
+
+impl Error for AsyncListenerSkipped {}
+
+/// The errors returned by the event managers and the event emitters.
+///
+/// # Example
+/// ```rust
+/// use emitix::{event_hub::EventHub, EmitixError, EventManager};
+/// use anyhow::bail;
+///
+/// let manager = EventHub::default();
+/// let listener_id = manager
+///     .add_fallible_listener("Events You Like", |_: String| bail!("Not today"))
+///     .unwrap();
+///
+/// match manager.emit("Events You Like", String::from("Test Event")) {
+///     Err(EmitixError::ListenerFailures(failures)) => assert_eq!(failures[0].id, listener_id),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EmitixError {
+    /// A lock protecting the shared data structures was poisoned by a panicking thread.
+    LockPoisoned {
+        /// The place where the poisoned lock was met.
+        context: String,
+    },
+    /// No listener was left for delivering an event of the kind.
+    NoListeners {
+        /// The event kind without listeners.
+        kind: String,
+    },
+    /// Some listeners failed while an event was emitted, the other listeners were called anyway.
+    ListenerFailures(Vec<ListenerFailure>),
+    /// The journal of the hub failed to record or to read events.
+    Journal(anyhow::Error),
+    /// An argument passed to an event manager is not valid, nothing was done.
+    InvalidArgument {
+        /// The name of the invalid argument.
+        argument: String,
+        /// The reason why the argument is not valid.
+        reason:   String,
+    },
+}
+
+impl EmitixError {
+    pub(crate) fn lock_poisoned(context: impl Into<String>) -> Self {
+        Self::LockPoisoned {
+            context: context.into(),
+        }
+    }
+
+    pub(crate) fn invalid_argument(argument: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::InvalidArgument {
+            argument: argument.into(),
+            reason:   reason.into(),
+        }
+    }
+
+    /// Returns a report of the error, which can be cloned and serialized unlike the error.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::{event_hub::EventHub, EventManager};
+    /// use anyhow::bail;
+    ///
+    /// let manager = EventHub::default();
+    /// manager
+    ///     .add_fallible_listener("Events You Like", |_: String| bail!("Not today"))
+    ///     .unwrap();
+    ///
+    /// let report = manager
+    ///     .emit("Events You Like", String::from("Test Event"))
+    ///     .unwrap_err()
+    ///     .report();
+    ///
+    /// assert_eq!(report.failures[0].kind, "Events You Like");
+    /// assert_eq!(report.failures[0].message, "Not today");
+    /// ```
+    pub fn report(&self) -> ErrorReport {
+        ErrorReport {
+            message:  self.to_string(),
+            failures: self.failures().iter().map(ListenerFailure::report).collect(),
+        }
+    }
+
+    /// Returns the listener failures carried by the error, which is empty for the other errors.
+    pub fn failures(&self) -> &[ListenerFailure] {
+        match self {
+            Self::ListenerFailures(failures) => failures,
+            _ => &[],
+        }
+    }
+}
+
+impl fmt::Display for EmitixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LockPoisoned {
+                context,
+            } => write!(f, "Lock poisoned in {context}"),
+            Self::NoListeners {
+                kind,
+            } => write!(f, "No listeners for event kind `{kind}`"),
+            Self::ListenerFailures(failures) => {
+                write!(f, "Failed to emit event:")?;
+                for failure in failures {
+                    write!(f, "\n  - {failure}")?;
+                }
+
+                Ok(())
+            }
+            Self::Journal(source) => write!(f, "Journal failure: {source}"),
+            Self::InvalidArgument {
+                argument,
+                reason,
+            } => write!(f, "Invalid argument `{argument}`: {reason}"),
+        }
+    }
+}
+
+impl Error for EmitixError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::ListenerFailures(failures) => failures.first().map(|failure| failure as &(dyn Error + 'static)),
+            Self::Journal(source) => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// A report of the failure of one listener, which can be logged or sent elsewhere.
+///
+/// With the `serde` feature, the report can be serialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FailureReport {
+    /// The identifier of the failing listener.
+    pub id:       Uuid,
+    /// The event kind the failing listener is registered for.
+    pub kind:     String,
+    /// The error returned by the listener, with its causes.
+    pub message:  String,
+    /// Whether the listener panicked.
+    pub panicked: bool,
+}
+
+/// A report of an [`EmitixError`], which can be logged or sent elsewhere.
+///
+/// With the `serde` feature, the report can be serialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorReport {
+    /// The message of the error.
+    pub message:  String,
+    /// The reports of the listener failures carried by the error.
+    pub failures: Vec<FailureReport>,
+}
+
+impl From<&EmitixError> for ErrorReport {
+    fn from(error: &EmitixError) -> Self {
+        error.report()
+    }
+}
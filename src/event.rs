@@ -0,0 +1,43 @@
+/// An event type whose variants are emitted for their own event kind.
+///
+/// It's usually derived with `#[derive(Event)]`, available with the `derive` feature,
+/// which also generates a constant with the kind of each variant,
+/// and typed `on_<variant>` and `emit_<variant>` helpers over [`EventHub`](crate::event_hub::EventHub).
+///
+/// # Example
+/// ```rust
+/// use emitix::{event_hub::EventHub, Event, EventManager};
+///
+/// #[derive(Clone)]
+/// enum OrderEvent {
+///     Placed(u32),
+///     Cancelled(u32),
+/// }
+///
+/// impl Event for OrderEvent {
+///     const KINDS: &'static [&'static str] = &["order_event.placed", "order_event.cancelled"];
+///
+///     fn kind(&self) -> &'static str {
+///         match self {
+///             Self::Placed(_) => Self::KINDS[0],
+///             Self::Cancelled(_) => Self::KINDS[1],
+///         }
+///     }
+/// }
+///
+/// let manager = EventHub::default();
+/// manager
+///     .add_listener("order_event.placed", |_: OrderEvent| {
+///         println!("Order placed")
+///     })
+///     .unwrap();
+///
+/// manager.emit_event(OrderEvent::Placed(42)).unwrap();
+/// ```
+pub trait Event: Clone + Send + Sync + 'static {
+    /// The event kinds of all the variants.
+    const KINDS: &'static [&'static str];
+
+    /// Returns the event kind of the variant.
+    fn kind(&self) -> &'static str;
+}
@@ -0,0 +1,115 @@
+use crate::EventMetadata;
+use std::{
+    any::Any,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
+
+/// The state of one emission, shared by the listeners called for it.
+///
+/// It's given to the listeners added with
+/// [`EventHub::add_contextual_listener`](super::EventHub::add_contextual_listener), which can stop
+/// the propagation of the event to the next listeners, or cancel the event like DOM events.
+///
+/// # Example
+/// ```rust
+/// use emitix::event_hub::{EventContext, EventHub};
+///
+/// let manager = EventHub::default();
+/// manager
+///     .add_contextual_listener("Delete File", |path: String, context: &EventContext| {
+///         if path.starts_with("/etc") {
+///             context.prevent_default();
+///         }
+///     })
+///     .unwrap();
+///
+/// assert!(
+///     manager
+///         .emit_cancellable("Delete File", String::from("/etc/hosts"))
+///         .unwrap()
+/// );
+/// ```
+#[derive(Default)]
+pub struct EventContext {
+    propagation_stopped: AtomicBool,
+    default_prevented:   AtomicBool,
+    metadata:            Option<EventMetadata>,
+    unstamped_metadata:  OnceLock<EventMetadata>,
+    event_kind:          Option<Box<dyn Any + Send + Sync>>,
+}
+
+impl EventContext {
+    /// Creates the context of an emission without metadata, for one event kind or for several ones.
+    pub(super) fn for_kind<K: Send + Sync + 'static>(event_kind: Option<K>) -> Self {
+        Self {
+            event_kind: event_kind.map(|event_kind| Box::new(event_kind) as Box<dyn Any + Send + Sync>),
+            ..Self::default()
+        }
+    }
+
+    /// Creates the context of an emission stamped with metadata, for one event kind or for several ones.
+    pub(super) fn stamped<K: Send + Sync + 'static>(metadata: EventMetadata, event_kind: Option<K>) -> Self {
+        Self {
+            metadata: Some(metadata),
+            event_kind: event_kind.map(|event_kind| Box::new(event_kind) as Box<dyn Any + Send + Sync>),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the metadata of the emission, when the hub is in envelope mode or the event is emitted from an envelope.
+    pub fn metadata(&self) -> Option<&EventMetadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Returns the metadata of the emission for the envelope listeners.
+    ///
+    /// Outside envelope mode, the metadata are created once for the emission, without sequence number,
+    /// so all the envelope listeners of the emission receive the same identifier.
+    pub(super) fn envelope_metadata(&self) -> EventMetadata {
+        match self.metadata {
+            Some(metadata) => metadata,
+            None => *self.unstamped_metadata.get_or_init(EventMetadata::root),
+        }
+    }
+
+    /// Returns the emitted event kind, when the emission has one event kind.
+    pub(super) fn event_kind<K: 'static>(&self) -> Option<&K> {
+        self.event_kind.as_ref()?.downcast_ref()
+    }
+
+    /// Stops the propagation of the event, so the next listeners are not called.
+    pub fn stop_propagation(&self) {
+        self.propagation_stopped.store(true, Ordering::Release);
+    }
+
+    /// Cancels the event, which is reported by [`EventHub::emit_cancellable`](super::EventHub::emit_cancellable).
+    ///
+    /// The next listeners are still called, unless the propagation is stopped too.
+    pub fn prevent_default(&self) {
+        self.default_prevented.store(true, Ordering::Release);
+    }
+
+    /// Checks if a listener stopped the propagation of the event.
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.propagation_stopped.load(Ordering::Acquire)
+    }
+
+    /// Checks if a listener cancelled the event.
+    pub fn is_default_prevented(&self) -> bool {
+        self.default_prevented.load(Ordering::Acquire)
+    }
+}
+
+impl fmt::Debug for EventContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventContext")
+            .field("propagation_stopped", &self.is_propagation_stopped())
+            .field("default_prevented", &self.is_default_prevented())
+            .field("metadata", &self.metadata)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,81 @@
+use crate::EventMetadata;
+use uuid::Uuid;
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+thread_local! {
+    /// The emission whose listeners are running on the current thread, if it was stamped.
+    static CURRENT_CAUSE: Cell<Option<Cause>> = const { Cell::new(None) };
+}
+
+/// The emission causing the events emitted by its listeners.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Cause {
+    pub(super) event_id:       Uuid,
+    pub(super) correlation_id: Uuid,
+}
+
+impl Cause {
+    pub(super) fn of(metadata: &EventMetadata) -> Self {
+        Self {
+            event_id:       metadata.id,
+            correlation_id: metadata.correlation_id,
+        }
+    }
+
+    /// Returns the emission whose listeners are running on the current thread.
+    pub(super) fn current() -> Option<Self> {
+        CURRENT_CAUSE.with(Cell::get)
+    }
+
+    /// Runs a function as a listener of the emission, the previous emission being restored afterward,
+    /// even if the function panics.
+    ///
+    /// Without emission, the function runs as a listener of the current emission.
+    pub(super) fn scope<R, F: FnOnce() -> R>(cause: Option<Self>, f: F) -> R {
+        let Some(cause) = cause else {
+            return f();
+        };
+
+        struct Restore(Option<Cause>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT_CAUSE.with(|current| current.set(self.0));
+            }
+        }
+
+        let _restore = Restore(CURRENT_CAUSE.with(|current| current.replace(Some(cause))));
+
+        f()
+    }
+}
+
+/// A future whose polls run as listeners of an emission, whatever the thread polling it.
+pub(super) struct CausedFuture<F: Future> {
+    cause:  Option<Cause>,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> CausedFuture<F> {
+    pub(super) fn new(cause: Option<Cause>, future: F) -> Self {
+        Self {
+            cause,
+            future: Box::pin(future),
+        }
+    }
+}
+
+impl<F: Future> Future for CausedFuture<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let cause = self.cause;
+
+        Cause::scope(cause, || self.future.as_mut().poll(cx))
+    }
+}
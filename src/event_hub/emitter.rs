@@ -1,27 +1,36 @@
-use super::listener::Listener;
-use crate::EventEmitter;
-use anyhow::Result;
+use crate::{EventEmitter, Result};
+use uuid::Uuid;
+use std::sync::Arc;
+
+type Dispatch<T> = Arc<dyn Fn(T) -> Result<()> + Send + Sync>;
 
 pub(super) struct EventHubEmitter<T: Clone + Send + Sync + 'static> {
-    listener: Listener<T>,
+    id:       Uuid,
+    dispatch: Dispatch<T>,
 }
 
 impl<T: Clone + Send + Sync + 'static> EventHubEmitter<T> {
-    pub(super) fn new(listener: Listener<T>) -> Self {
+    pub(super) fn new<F: Fn(T) -> Result<()> + Send + Sync + 'static>(id: Uuid, dispatch: F) -> Self {
         Self {
-            listener
+            id,
+            dispatch: Arc::new(dispatch),
         }
     }
 }
 
 impl<T: Clone + Send + Sync + 'static> EventEmitter<T> for EventHubEmitter<T> {
     fn emit(&self, event_arg: T) -> Result<()> {
-        self.listener.call(event_arg)
+        (self.dispatch)(event_arg)
     }
 
     fn clone(&self) -> Box<dyn EventEmitter<T>> {
         Box::new(Self {
-            listener: self.listener.clone(),
+            id:       self.id,
+            dispatch: Arc::clone(&self.dispatch),
         })
     }
+
+    fn id(&self) -> Option<Uuid> {
+        Some(self.id)
+    }
 }
@@ -0,0 +1,664 @@
+use super::journal::{JournalEntry, JournalSink, SequenceRange};
+use crate::{EmitixError, EventKey, Result};
+use anyhow::{bail, Context};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, Read, Write},
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+/// The size of the header of a record, which is the length of the payload then its CRC32, in little endian.
+const RECORD_HEADER_SIZE: u64 = 8;
+
+/// The extension of the segment files, which are named after the sequence number of their first record.
+const SEGMENT_EXTENSION: &str = "log";
+
+/// When the segments of a [`FileJournal`] are synchronized with the disk.
+///
+/// Whatever the policy, a full segment is synchronized before the next one is started,
+/// so only the last segment can have a record torn by a crash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FsyncPolicy {
+    /// Synchronizes the segment after each record, it's the default policy.
+    #[default]
+    Always,
+    /// Synchronizes the segment after every `n` records.
+    Every(u32),
+    /// Leaves the synchronization to the operating system, unless [`FileJournal::sync`] is called
+    /// or the segment is full.
+    Never,
+}
+
+/// The options of a [`FileJournal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileJournalOptions {
+    max_segment_size: u64,
+    fsync:            FsyncPolicy,
+}
+
+impl FileJournalOptions {
+    /// Sets the size from which the records are written in a new segment, which is 64 MiB by default.
+    ///
+    /// A segment holds one record at least, even if the record is larger.
+    pub fn with_max_segment_size(self, max_segment_size: u64) -> Self {
+        Self {
+            max_segment_size,
+            ..self
+        }
+    }
+
+    /// Sets when the segments are synchronized with the disk.
+    pub fn with_fsync(self, fsync: FsyncPolicy) -> Self {
+        Self {
+            fsync,
+            ..self
+        }
+    }
+
+    /// Returns the size from which the records are written in a new segment.
+    pub fn max_segment_size(&self) -> u64 {
+        self.max_segment_size
+    }
+
+    /// Returns when the segments are synchronized with the disk.
+    pub fn fsync(&self) -> FsyncPolicy {
+        self.fsync
+    }
+}
+
+impl Default for FileJournalOptions {
+    fn default() -> Self {
+        Self {
+            max_segment_size: 64 * 1024 * 1024,
+            fsync:            FsyncPolicy::default(),
+        }
+    }
+}
+
+/// The part of an entry read for finding the last sequence number of a segment.
+#[derive(Deserialize)]
+struct SequenceHeader {
+    sequence: u64,
+}
+
+/// The outcome of reading a record from a segment.
+enum RecordRead {
+    Record(Vec<u8>),
+    End,
+    Torn,
+}
+
+/// The segment in which the records are appended.
+struct SegmentWriter {
+    file:          File,
+    size:          u64,
+    unsynced:      u32,
+    next_sequence: u64,
+}
+
+struct FileJournalInner {
+    dir:     PathBuf,
+    options: FileJournalOptions,
+    writer:  Mutex<SegmentWriter>,
+}
+
+/// A journal sink storing the entries in files, so the events survive a restart or a crash.
+///
+/// The entries are written as records in segment files, in a directory.
+/// Each record is the length of the entry serialized in JSON and its CRC32 checksum, followed by the entry,
+/// and a new segment is started when the current one is full.
+///
+/// When the journal is reopened, the last segment is truncated after its last valid record,
+/// so a record torn by a crash is dropped instead of breaking the journal.
+/// The full segments are synchronized before the next one is started, so they can't have torn records.
+///
+/// It's available with the `file-journal` feature.
+///
+/// # Example
+/// ```rust
+/// use emitix::event_hub::{EventHub, EventJournal, FileJournal};
+///
+/// let dir = std::env::temp_dir().join(format!("emitix-journal-{}", std::process::id()));
+/// # let _ = std::fs::remove_dir_all(&dir);
+/// let file = FileJournal::<String>::open(&dir).unwrap();
+///
+/// let manager = EventHub::default();
+/// manager
+///     .attach_journal(EventJournal::new(file.clone()).unwrap())
+///     .unwrap();
+/// manager.emit("user.created", String::from("Alice")).unwrap();
+/// manager.emit("order.placed", String::from("42")).unwrap();
+///
+/// let reopened = FileJournal::<String>::open(&dir).unwrap();
+/// let users = reopened
+///     .records(..)
+///     .unwrap()
+///     .of_kind(String::from("user.*"))
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(users.len(), 1);
+/// assert_eq!(users[0].payload, "Alice");
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+///
+/// A torn record is dropped when the journal is reopened, and the next records are appended in its place:
+/// ```rust
+/// use emitix::event_hub::{EventHub, EventJournal, FileJournal};
+/// use std::{
+///     fs::{self, OpenOptions},
+///     io::Write,
+/// };
+///
+/// let dir = std::env::temp_dir().join(format!("emitix-torn-journal-{}", std::process::id()));
+/// # let _ = fs::remove_dir_all(&dir);
+/// let manager = EventHub::default();
+/// manager
+///     .attach_journal(EventJournal::new(FileJournal::<String>::open(&dir).unwrap()).unwrap())
+///     .unwrap();
+/// manager.emit("user.created", String::from("Alice")).unwrap();
+/// manager.detach_journal().unwrap();
+///
+/// // A crash left a record whose checksum doesn't match its payload.
+/// let segment = fs::read_dir(&dir).unwrap().next().unwrap().unwrap().path();
+/// let valid_size = fs::metadata(&segment).unwrap().len();
+/// OpenOptions::new()
+///     .append(true)
+///     .open(&segment)
+///     .unwrap()
+///     .write_all(&[4, 0, 0, 0, 0, 0, 0, 0, b'j', b'u', b'n', b'k'])
+///     .unwrap();
+///
+/// let reopened = FileJournal::<String>::open(&dir).unwrap();
+/// assert_eq!(fs::metadata(&segment).unwrap().len(), valid_size);
+///
+/// manager
+///     .attach_journal(EventJournal::new(reopened.clone()).unwrap())
+///     .unwrap();
+/// manager.emit("user.created", String::from("Bob")).unwrap();
+///
+/// let records = reopened
+///     .records(..)
+///     .unwrap()
+///     .map(|record| record.map(|entry| (entry.sequence, entry.payload)))
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(
+///     records,
+///     vec![(0, String::from("Alice")), (1, String::from("Bob"))]
+/// );
+/// # fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub struct FileJournal<T, K = String> {
+    inner:  Arc<FileJournalInner>,
+    marker: PhantomData<fn() -> (T, K)>,
+}
+
+impl<T, K> FileJournal<T, K>
+where
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+    K: EventKey + Serialize + DeserializeOwned,
+{
+    /// Opens the journal stored in a directory with the default options, creating it if needed.
+    ///
+    /// # Arguments
+    /// - `dir`: The directory of the segment files.
+    ///
+    /// # Returns
+    /// - `Ok(FileJournal)` which appends the records after the valid records of the directory.
+    /// - `Err(EmitixError::Journal)` if the directory or its segments can't be read or written.
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        Self::open_with_options(dir, FileJournalOptions::default())
+    }
+
+    /// Opens the journal stored in a directory, creating it if needed.
+    ///
+    /// # Arguments
+    /// - `dir`: The directory of the segment files.
+    /// - `options`: The segment size and the synchronization policy.
+    ///
+    /// # Returns
+    /// - `Ok(FileJournal)` which appends the records after the valid records of the directory.
+    /// - `Err(EmitixError::Journal)` if the directory or its segments can't be read or written.
+    pub fn open_with_options<P: AsRef<Path>>(dir: P, options: FileJournalOptions) -> Result<Self> {
+        let inner = recover(dir.as_ref(), options).map_err(EmitixError::Journal)?;
+
+        Ok(Self {
+            inner:  Arc::new(inner),
+            marker: PhantomData,
+        })
+    }
+
+    /// Returns the directory of the segment files.
+    pub fn dir(&self) -> &Path {
+        &self.inner.dir
+    }
+
+    /// Synchronizes the current segment with the disk, whatever the synchronization policy.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the segment was synchronized.
+    /// - `Err(EmitixError::Journal)` if the synchronization failed.
+    pub fn sync(&self) -> Result<()> {
+        let mut writer = self
+            .inner
+            .writer
+            .lock()
+            .map_err(|_| EmitixError::lock_poisoned("file journal"))?;
+
+        writer
+            .file
+            .sync_data()
+            .context("Failed to synchronize the journal segment")
+            .map_err(EmitixError::Journal)?;
+
+        writer.unsynced = 0;
+        Ok(())
+    }
+
+    /// Reads the records whose sequence number is in a range, in sequence order.
+    ///
+    /// The records appended while they are read may be missed.
+    ///
+    /// # Arguments
+    /// - `range`: The range of the sequence numbers of the records, like `..` for all the records.
+    ///
+    /// # Returns
+    /// - `Ok(JournalRecords<T, K>)` which iterates over the records.
+    /// - `Err(EmitixError::Journal)` if the segments can't be listed.
+    pub fn records<R: RangeBounds<u64>>(&self, range: R) -> Result<JournalRecords<T, K>> {
+        let range = (range.start_bound().cloned(), range.end_bound().cloned());
+
+        self.records_in(range).map_err(EmitixError::Journal)
+    }
+
+    fn records_in(&self, range: SequenceRange) -> anyhow::Result<JournalRecords<T, K>> {
+        let segments = list_segments(&self.inner.dir)?;
+        let start = match range.0 {
+            Bound::Included(start) => start,
+            Bound::Excluded(start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+
+        // A segment is skipped when the next one starts before the range, or when it starts after the range.
+        let segments = segments
+            .iter()
+            .enumerate()
+            .filter(|(i, (first, _))| {
+                let before = segments.get(i + 1).is_some_and(|(next, _)| *next <= start);
+                let after = match range.1 {
+                    Bound::Included(end) => *first > end,
+                    Bound::Excluded(end) => *first >= end,
+                    Bound::Unbounded => false,
+                };
+
+                !before && !after
+            })
+            .map(|(_, (_, path))| path.clone())
+            .collect();
+
+        Ok(JournalRecords {
+            segments,
+            current: None,
+            range,
+            kind: None,
+            done: false,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<T, K> Clone for FileJournal<T, K> {
+    fn clone(&self) -> Self {
+        Self {
+            inner:  Arc::clone(&self.inner),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, K> JournalSink<T, K> for FileJournal<T, K>
+where
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+    K: EventKey + Serialize + DeserializeOwned,
+{
+    fn append(&self, entry: JournalEntry<T, K>) -> anyhow::Result<()> {
+        let payload = serde_json::to_vec(&entry).context("Failed to serialize the journal entry")?;
+        let len = u32::try_from(payload.len()).context("The journal entry is too large")?;
+        let mut record = Vec::with_capacity(RECORD_HEADER_SIZE as usize + payload.len());
+
+        record.extend(len.to_le_bytes());
+        record.extend(crc32fast::hash(&payload).to_le_bytes());
+        record.extend(payload);
+
+        let mut writer = self
+            .inner
+            .writer
+            .lock()
+            .map_err(|_| EmitixError::lock_poisoned("file journal"))?;
+
+        let options = &self.inner.options;
+        if writer.size > 0 && writer.size + record.len() as u64 > options.max_segment_size {
+            // Only the last segment is recovered when the journal is reopened, so the full one must be durable.
+            writer
+                .file
+                .sync_data()
+                .context("Failed to synchronize the full journal segment")?;
+
+            writer.file = create_segment(&self.inner.dir, entry.sequence)?;
+            writer.size = 0;
+            writer.unsynced = 0;
+        }
+
+        if let Err(err) = writer.file.write_all(&record) {
+            // A partial record is removed, so the next records are not written after it.
+            let _ = writer.file.set_len(writer.size);
+
+            return Err(err).context("Failed to write the journal record");
+        }
+
+        writer.size += record.len() as u64;
+        writer.unsynced += 1;
+        writer.next_sequence = entry.sequence + 1;
+
+        let sync = match options.fsync {
+            FsyncPolicy::Always => true,
+            FsyncPolicy::Every(records) => writer.unsynced >= records,
+            FsyncPolicy::Never => false,
+        };
+
+        if sync {
+            writer
+                .file
+                .sync_data()
+                .context("Failed to synchronize the journal segment")?;
+            writer.unsynced = 0;
+        }
+
+        Ok(())
+    }
+
+    fn read(&self, range: SequenceRange) -> anyhow::Result<Vec<JournalEntry<T, K>>> {
+        let mut records = self.records_in(range)?;
+        let mut entries = Vec::new();
+
+        while let Some(entry) = records.next_entry()? {
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+
+    fn next_sequence(&self) -> anyhow::Result<u64> {
+        let writer = self
+            .inner
+            .writer
+            .lock()
+            .map_err(|_| EmitixError::lock_poisoned("file journal"))?;
+
+        Ok(writer.next_sequence)
+    }
+}
+
+/// The segment being read by [`JournalRecords`].
+struct SegmentReader {
+    path:      PathBuf,
+    reader:    BufReader<File>,
+    remaining: u64,
+    is_last:   bool,
+}
+
+/// An iterator over the records of a [`FileJournal`], in sequence order.
+pub struct JournalRecords<T, K = String> {
+    segments: VecDeque<PathBuf>,
+    current:  Option<SegmentReader>,
+    range:    SequenceRange,
+    kind:     Option<K>,
+    done:     bool,
+    marker:   PhantomData<fn() -> T>,
+}
+
+impl<T, K> JournalRecords<T, K>
+where
+    T: DeserializeOwned,
+    K: EventKey + DeserializeOwned,
+{
+    /// Keeps only the records of an event kind, or of the event kinds matching a pattern.
+    pub fn of_kind(self, kind: K) -> Self {
+        Self {
+            kind: Some(kind),
+            ..self
+        }
+    }
+
+    fn next_entry(&mut self) -> anyhow::Result<Option<JournalEntry<T, K>>> {
+        while !self.done {
+            let Some(segment) = self.current.as_mut() else {
+                match self.segments.pop_front() {
+                    Some(path) => {
+                        let file = File::open(&path)
+                            .with_context(|| format!("Failed to open the journal segment `{}`", path.display()))?;
+
+                        self.current = Some(SegmentReader {
+                            remaining: file.metadata()?.len(),
+                            reader: BufReader::new(file),
+                            is_last: self.segments.is_empty(),
+                            path,
+                        });
+                    }
+                    None => self.done = true,
+                }
+
+                continue;
+            };
+
+            let payload = match read_record(&mut segment.reader, segment.remaining)? {
+                RecordRead::Record(payload) => payload,
+                // The last record of the last segment can be in the middle of its writing.
+                RecordRead::Torn if segment.is_last => {
+                    self.current = None;
+
+                    continue;
+                }
+                RecordRead::Torn => bail!(
+                    "The journal segment `{}` has a corrupted record",
+                    segment.path.display()
+                ),
+                RecordRead::End => {
+                    self.current = None;
+
+                    continue;
+                }
+            };
+
+            segment.remaining -= RECORD_HEADER_SIZE + payload.len() as u64;
+
+            let entry = serde_json::from_slice::<JournalEntry<T, K>>(&payload)
+                .with_context(|| format!("Failed to deserialize a record of `{}`", segment.path.display()))?;
+
+            let past_end = match self.range.1 {
+                Bound::Included(end) => entry.sequence > end,
+                Bound::Excluded(end) => entry.sequence >= end,
+                Bound::Unbounded => false,
+            };
+
+            if past_end {
+                self.done = true;
+            } else if self.range.contains(&entry.sequence)
+                && self
+                    .kind
+                    .as_ref()
+                    .is_none_or(|kind| *kind == entry.kind || (kind.is_pattern() && kind.matches(&entry.kind)))
+            {
+                return Ok(Some(entry));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl<T, K> Iterator for JournalRecords<T, K>
+where
+    T: DeserializeOwned,
+    K: EventKey + DeserializeOwned,
+{
+    type Item = Result<JournalEntry<T, K>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_entry() {
+            Ok(entry) => entry.map(Ok),
+            Err(source) => {
+                self.done = true;
+
+                Some(Err(EmitixError::Journal(source)))
+            }
+        }
+    }
+}
+
+/// Opens the journal of a directory, truncating its last segment after its last valid record.
+fn recover(dir: &Path, options: FileJournalOptions) -> anyhow::Result<FileJournalInner> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create the journal directory `{}`", dir.display()))?;
+
+    let writer = match list_segments(dir)?.pop() {
+        None => SegmentWriter {
+            file:          create_segment(dir, 0)?,
+            size:          0,
+            unsynced:      0,
+            next_sequence: 0,
+        },
+        Some((first, path)) => {
+            let (valid_size, last_sequence) = scan_segment(&path)?;
+            let file = OpenOptions::new()
+                .append(true)
+                .open(&path)
+                .with_context(|| format!("Failed to open the journal segment `{}`", path.display()))?;
+
+            if file.metadata()?.len() > valid_size {
+                file.set_len(valid_size)
+                    .and_then(|_| file.sync_all())
+                    .with_context(|| format!("Failed to truncate the journal segment `{}`", path.display()))?;
+            }
+
+            SegmentWriter {
+                file,
+                size: valid_size,
+                unsynced: 0,
+                next_sequence: last_sequence.map_or(first, |sequence| sequence + 1),
+            }
+        }
+    };
+
+    Ok(FileJournalInner {
+        dir: dir.to_path_buf(),
+        options,
+        writer: Mutex::new(writer),
+    })
+}
+
+/// Lists the segments of a directory with the sequence number of their first record, in sequence order.
+fn list_segments(dir: &Path) -> anyhow::Result<Vec<(u64, PathBuf)>> {
+    let mut segments = vec![];
+
+    for entry in
+        fs::read_dir(dir).with_context(|| format!("Failed to list the journal segments of `{}`", dir.display()))?
+    {
+        let path = entry?.path();
+        let first = path
+            .extension()
+            .filter(|extension| *extension == SEGMENT_EXTENSION)
+            .and(path.file_stem())
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse::<u64>().ok());
+
+        if let Some(first) = first {
+            segments.push((first, path));
+        }
+    }
+
+    segments.sort_by_key(|(first, _)| *first);
+
+    Ok(segments)
+}
+
+/// Creates the segment whose first record has a sequence number.
+fn create_segment(dir: &Path, first: u64) -> anyhow::Result<File> {
+    let path = dir.join(format!("{first:020}.{SEGMENT_EXTENSION}"));
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to create the journal segment `{}`", path.display()))?;
+
+    sync_directory(dir).with_context(|| format!("Failed to synchronize the journal directory `{}`", dir.display()))?;
+
+    Ok(file)
+}
+
+/// Returns the size of the valid records at the start of a segment, and the sequence number of the last one.
+fn scan_segment(path: &Path) -> anyhow::Result<(u64, Option<u64>)> {
+    let file = File::open(path).with_context(|| format!("Failed to open the journal segment `{}`", path.display()))?;
+    let len = file.metadata()?.len();
+    let mut reader = BufReader::new(file);
+    let mut valid_size = 0;
+    let mut last_sequence = None;
+
+    while let RecordRead::Record(payload) = read_record(&mut reader, len - valid_size)? {
+        let Ok(header) = serde_json::from_slice::<SequenceHeader>(&payload) else {
+            break;
+        };
+
+        valid_size += RECORD_HEADER_SIZE + payload.len() as u64;
+        last_sequence = Some(header.sequence);
+    }
+
+    Ok((valid_size, last_sequence))
+}
+
+/// Reads the next record of a segment, whose remaining size is given.
+fn read_record<R: Read>(reader: &mut R, remaining: u64) -> io::Result<RecordRead> {
+    if remaining == 0 {
+        return Ok(RecordRead::End);
+    }
+
+    if remaining < RECORD_HEADER_SIZE {
+        return Ok(RecordRead::Torn);
+    }
+
+    let mut header = [0; RECORD_HEADER_SIZE as usize];
+    reader.read_exact(&mut header)?;
+
+    let [l0, l1, l2, l3, c0, c1, c2, c3] = header;
+    let len = u32::from_le_bytes([l0, l1, l2, l3]);
+    let checksum = u32::from_le_bytes([c0, c1, c2, c3]);
+    if u64::from(len) > remaining - RECORD_HEADER_SIZE {
+        return Ok(RecordRead::Torn);
+    }
+
+    let mut payload = vec![0; len as usize];
+    reader.read_exact(&mut payload)?;
+    if crc32fast::hash(&payload) != checksum {
+        return Ok(RecordRead::Torn);
+    }
+
+    Ok(RecordRead::Record(payload))
+}
+
+/// Makes the creation of a segment durable.
+#[cfg(unix)]
+fn sync_directory(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+/// Makes the creation of a segment durable, which is done with the segment itself outside Unix.
+#[cfg(not(unix))]
+fn sync_directory(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
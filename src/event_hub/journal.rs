@@ -0,0 +1,256 @@
+use super::EventHub;
+use crate::{EmitixError, EventKey, Result};
+use std::{
+    ops::{Bound, RangeBounds},
+    sync::{Arc, Mutex, RwLock},
+    time::SystemTime,
+};
+
+/// The range of sequence numbers read from a journal.
+pub type SequenceRange = (Bound<u64>, Bound<u64>);
+
+/// An event recorded in a journal.
+///
+/// With the `serde` feature, the entries can be serialized when the event kind and the payload can.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JournalEntry<T, K = String> {
+    /// The position of the event in the journal, starting at `0`.
+    pub sequence:  u64,
+    /// The time when the event was emitted.
+    pub timestamp: SystemTime,
+    /// The event kind the event was emitted for.
+    pub kind:      K,
+    /// The event argument passed to the listeners.
+    pub payload:   T,
+}
+
+/// The storage of the entries of an [`EventJournal`].
+///
+/// The entries are appended in sequence order, and are never modified nor removed by the journal.
+pub trait JournalSink<T, K = String>: Send + Sync + 'static {
+    /// Appends an entry to the storage.
+    fn append(&self, entry: JournalEntry<T, K>) -> anyhow::Result<()>;
+
+    /// Reads the entries whose sequence number is in a range, in sequence order.
+    fn read(&self, range: SequenceRange) -> anyhow::Result<Vec<JournalEntry<T, K>>>;
+
+    /// Returns the sequence number of the next entry, which is `0` for an empty storage.
+    fn next_sequence(&self) -> anyhow::Result<u64>;
+}
+
+/// A journal sink keeping the entries in memory.
+pub struct MemoryJournal<T, K = String> {
+    entries: RwLock<Vec<JournalEntry<T, K>>>,
+}
+
+impl<T, K> MemoryJournal<T, K> {
+    /// Creates an empty journal sink.
+    pub fn new() -> Self {
+        Self {
+            entries: RwLock::new(Vec::new()),
+        }
+    }
+}
+
+impl<T, K> Default for MemoryJournal<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static, K: EventKey> JournalSink<T, K> for MemoryJournal<T, K> {
+    fn append(&self, entry: JournalEntry<T, K>) -> anyhow::Result<()> {
+        let mut entries = self
+            .entries
+            .write()
+            .map_err(|_| EmitixError::lock_poisoned("memory journal"))?;
+
+        entries.push(entry);
+        Ok(())
+    }
+
+    fn read(&self, range: SequenceRange) -> anyhow::Result<Vec<JournalEntry<T, K>>> {
+        let entries = self
+            .entries
+            .read()
+            .map_err(|_| EmitixError::lock_poisoned("memory journal"))?;
+
+        Ok(entries
+            .iter()
+            .filter(|entry| range.contains(&entry.sequence))
+            .cloned()
+            .collect())
+    }
+
+    fn next_sequence(&self) -> anyhow::Result<u64> {
+        let entries = self
+            .entries
+            .read()
+            .map_err(|_| EmitixError::lock_poisoned("memory journal"))?;
+
+        Ok(entries.last().map_or(0, |entry| entry.sequence + 1))
+    }
+}
+
+/// `EventJournal` is an append-only record of the events emitted by the hubs it's attached to.
+///
+/// Each event is recorded with its sequence number, its emission time, its event kind and its payload,
+/// before it's passed to the listeners.
+/// The recorded events can be read back, and replayed to a hub for debugging or for rebuilding a state.
+///
+/// The entries are stored by a [`JournalSink`], in memory by default.
+///
+/// # Example
+/// ```rust
+/// use emitix::{
+///     event_hub::{EventHub, EventJournal},
+///     EventManager,
+/// };
+///
+/// let manager = EventHub::default();
+/// let journal = EventJournal::in_memory();
+///
+/// manager.attach_journal(journal.clone()).unwrap();
+/// manager.emit("account.credited", 100).unwrap();
+/// manager.emit("account.debited", 30).unwrap();
+///
+/// let rebuilt = EventHub::default();
+/// rebuilt
+///     .add_listener("account.credited", |amount: i32| {
+///         println!("Credited {amount}")
+///     })
+///     .unwrap();
+///
+/// assert_eq!(journal.replay(.., &rebuilt).unwrap(), 2);
+/// assert_eq!(journal.entries(1..).unwrap()[0].kind, "account.debited");
+/// ```
+pub struct EventJournal<T: Clone + Send + Sync + 'static, K: EventKey = String> {
+    sink:          Arc<dyn JournalSink<T, K>>,
+    next_sequence: Arc<Mutex<u64>>,
+}
+
+impl<T: Clone + Send + Sync + 'static, K: EventKey> EventJournal<T, K> {
+    /// Creates a journal storing its entries with a sink.
+    ///
+    /// The sequence numbers continue after the entries already stored by the sink.
+    ///
+    /// # Arguments
+    /// - `sink`: The storage of the entries.
+    ///
+    /// # Returns
+    /// - `Ok(EventJournal)` which records the events in the sink.
+    /// - `Err(EmitixError::Journal)` if the sink failed to give its next sequence number.
+    pub fn new<S: JournalSink<T, K>>(sink: S) -> Result<Self> {
+        let next_sequence = sink.next_sequence().map_err(EmitixError::Journal)?;
+
+        Ok(Self {
+            sink:          Arc::new(sink),
+            next_sequence: Arc::new(Mutex::new(next_sequence)),
+        })
+    }
+
+    /// Creates a journal keeping its entries in memory.
+    pub fn in_memory() -> Self {
+        Self {
+            sink:          Arc::new(MemoryJournal::new()),
+            next_sequence: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    /// Records an event at the end of the journal.
+    ///
+    /// # Returns
+    /// - `Ok(u64)` which is the sequence number of the recorded event.
+    /// - `Err(EmitixError)` if the sink failed to store the event, or if access to the `Mutex` failed.
+    pub(super) fn record(&self, kind: K, payload: T) -> Result<u64> {
+        // The sequence is locked while the entry is appended, so the entries are appended in sequence order.
+        let mut next_sequence = self
+            .next_sequence
+            .lock()
+            .map_err(|_| EmitixError::lock_poisoned("event journal"))?;
+
+        let sequence = *next_sequence;
+        let entry = JournalEntry {
+            sequence,
+            timestamp: SystemTime::now(),
+            kind,
+            payload,
+        };
+
+        self.sink.append(entry).map_err(EmitixError::Journal)?;
+        *next_sequence += 1;
+
+        Ok(sequence)
+    }
+
+    /// Returns the number of recorded events.
+    ///
+    /// # Returns
+    /// - `Ok(u64)` which is also the sequence number of the next recorded event.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn len(&self) -> Result<u64> {
+        let next_sequence = self
+            .next_sequence
+            .lock()
+            .map_err(|_| EmitixError::lock_poisoned("event journal"))?;
+
+        Ok(*next_sequence)
+    }
+
+    /// Checks if no event was recorded.
+    ///
+    /// # Returns
+    /// - `Ok(bool)` indicating whether the journal is empty.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Reads the recorded events whose sequence number is in a range.
+    ///
+    /// # Arguments
+    /// - `range`: The range of the sequence numbers of the events, like `..` for all the events.
+    ///
+    /// # Returns
+    /// - `Ok(Vec<JournalEntry<T, K>>)` with the events, in sequence order.
+    /// - `Err(EmitixError::Journal)` if the sink failed to read the events.
+    pub fn entries<R: RangeBounds<u64>>(&self, range: R) -> Result<Vec<JournalEntry<T, K>>> {
+        let range = (range.start_bound().cloned(), range.end_bound().cloned());
+
+        self.sink.read(range).map_err(EmitixError::Journal)
+    }
+
+    /// Emits again the recorded events whose sequence number is in a range, in sequence order.
+    ///
+    /// The events are emitted to the target hub with [`EventHub::emit`],
+    /// so they are recorded again if a journal is attached to the target hub.
+    ///
+    /// # Arguments
+    /// - `range`: The range of the sequence numbers of the events, like `..` for all the events.
+    /// - `target`: The hub to which the events are emitted.
+    ///
+    /// # Returns
+    /// - `Ok(usize)` which is the number of replayed events.
+    /// - `Err(EmitixError::Journal)` if the sink failed to read the events.
+    /// - `Err(EmitixError)` if an emission failed, the next events are not replayed.
+    pub fn replay<R: RangeBounds<u64>>(&self, range: R, target: &EventHub<T, K>) -> Result<usize> {
+        let entries = self.entries(range)?;
+        let count = entries.len();
+
+        for entry in entries {
+            target.emit(&entry.kind, entry.payload)?;
+        }
+
+        Ok(count)
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static, K: EventKey> Clone for EventJournal<T, K> {
+    fn clone(&self) -> Self {
+        Self {
+            sink:          Arc::clone(&self.sink),
+            next_sequence: Arc::clone(&self.next_sequence),
+        }
+    }
+}
@@ -1,22 +1,160 @@
+use super::context::EventContext;
+use crate::{AsyncListenerSkipped, ListenerPanic};
 use anyhow::{Error, Result};
-use std::sync::{Arc, Mutex};
+use futures::{future::BoxFuture, FutureExt};
+use std::{
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+        Condvar,
+        Mutex,
+        PoisonError,
+    },
+    thread::{self, ThreadId},
+};
+
+type SyncCallback<T> = Arc<Mutex<Box<dyn FnMut(T, &EventContext) -> Result<()> + Send + Sync>>>;
+type AsyncCallback<T> = Arc<dyn Fn(T) -> BoxFuture<'static, Result<()>> + Send + Sync>;
+
+#[derive(Clone)]
+enum Callback<T: Clone + Send + Sync + 'static> {
+    Sync(SyncCallback<T>),
+    Async(AsyncCallback<T>),
+}
+
+/// Holds back the calls of a listener from the other threads while it receives its past events,
+/// so it receives them before the events emitted meanwhile.
+struct ReplayGate {
+    replaying: Mutex<Option<ThreadId>>,
+    opened:    Condvar,
+}
+
+impl ReplayGate {
+    /// Waits until the gate is opened, unless the current thread is the one replaying the past events.
+    fn pass(&self) {
+        let current = thread::current().id();
+        let mut replaying = self.replaying.lock().unwrap_or_else(PoisonError::into_inner);
+
+        while replaying.is_some_and(|owner| owner != current) {
+            replaying = self.opened.wait(replaying).unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    fn open(&self) {
+        *self.replaying.lock().unwrap_or_else(PoisonError::into_inner) = None;
+        self.opened.notify_all();
+    }
+}
+
+/// Opens the gate of a listener when it's dropped, once the listener has received its past events,
+/// even if passing them failed or panicked.
+pub(super) struct GateGuard(Arc<ReplayGate>);
+
+impl Drop for GateGuard {
+    fn drop(&mut self) {
+        self.0.open();
+    }
+}
 
 #[derive(Clone)]
 pub(super) struct Listener<T: Clone + Send + Sync + 'static> {
-    callback: Arc<Mutex<Box<dyn FnMut(T) -> Result<()> + Send + Sync>>>,
+    callback:    Callback<T>,
+    quarantined: Arc<AtomicBool>,
+    gate:        Option<Arc<ReplayGate>>,
 }
 
 impl<T: Clone + Send + Sync + 'static> Listener<T> {
-    pub(super) fn new<F: FnMut(T) -> Result<()> + Send + Sync + 'static>(callback: F) -> Self {
+    pub(super) fn new<F: FnMut(T) -> Result<()> + Send + Sync + 'static>(mut callback: F) -> Self {
+        Self::new_contextual(move |event_arg, _| callback(event_arg))
+    }
+
+    pub(super) fn new_contextual<F>(callback: F) -> Self
+    where
+        F: FnMut(T, &EventContext) -> Result<()> + Send + Sync + 'static, {
         Self {
-            callback: Arc::new(Mutex::new(Box::new(callback))),
+            callback:    Callback::Sync(Arc::new(Mutex::new(Box::new(callback)))),
+            quarantined: Arc::new(AtomicBool::new(false)),
+            gate:        None,
         }
     }
 
-    pub(super) fn call(&self, event_arg: T) -> Result<()> {
-        match self.callback.lock() {
-            Err(e) => Err(Error::msg(format!("Failed to lock listener callback: {e}"))),
-            Ok(mut cb) => cb(event_arg),
+    /// Creates a listener which receives past events on the current thread before it can be called
+    /// by the other threads, which wait until the returned guard is dropped.
+    pub(super) fn new_gated<F: FnMut(T) -> Result<()> + Send + Sync + 'static>(callback: F) -> (Self, GateGuard) {
+        let gate = Arc::new(ReplayGate {
+            replaying: Mutex::new(Some(thread::current().id())),
+            opened:    Condvar::new(),
+        });
+
+        let listener = Self {
+            gate: Some(Arc::clone(&gate)),
+            ..Self::new(callback)
+        };
+
+        (listener, GateGuard(gate))
+    }
+
+    pub(super) fn new_async<F, Fut>(callback: F) -> Self
+    where
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static, {
+        Self {
+            callback:    Callback::Async(Arc::new(move |event_arg| Box::pin(callback(event_arg)))),
+            quarantined: Arc::new(AtomicBool::new(false)),
+            gate:        None,
+        }
+    }
+
+    pub(super) fn is_quarantined(&self) -> bool {
+        self.quarantined.load(Ordering::Acquire)
+    }
+
+    pub(super) fn set_quarantined(&self, quarantined: bool) {
+        self.quarantined.store(quarantined, Ordering::Release);
+    }
+
+    /// Calls the listener on the current thread.
+    ///
+    /// Asynchronous listeners are not called, as the current thread would be blocked until their future resolves,
+    /// and an [`AsyncListenerSkipped`] error is returned instead.
+    ///
+    /// When `catch_panics` is set, a panic is caught before it can poison the listener lock,
+    /// and it's returned as a [`ListenerPanic`] error.
+    ///
+    /// While the listener receives its past events on another thread, the call waits until they are all received.
+    pub(super) fn call(&self, event_arg: T, context: &EventContext, catch_panics: bool) -> Result<()> {
+        if let Some(gate) = &self.gate {
+            gate.pass();
+        }
+
+        match &self.callback {
+            Callback::Sync(callback) => match callback.lock() {
+                Err(e) => Err(Error::msg(format!("Failed to lock listener callback: {e}"))),
+                Ok(mut cb) if catch_panics => catch_unwind(AssertUnwindSafe(|| cb(event_arg, context)))
+                    .unwrap_or_else(|payload| Err(ListenerPanic::from_payload(payload).into())),
+                Ok(mut cb) => cb(event_arg, context),
+            },
+            Callback::Async(_) => Err(AsyncListenerSkipped.into()),
+        }
+    }
+
+    pub(super) async fn call_async(&self, event_arg: T, context: &EventContext, catch_panics: bool) -> Result<()> {
+        match &self.callback {
+            Callback::Sync(_) => self.call(event_arg, context, catch_panics),
+            Callback::Async(callback) if catch_panics => {
+                let fut = match catch_unwind(AssertUnwindSafe(|| callback(event_arg))) {
+                    Ok(fut) => fut,
+                    Err(payload) => return Err(ListenerPanic::from_payload(payload).into()),
+                };
+
+                AssertUnwindSafe(fut)
+                    .catch_unwind()
+                    .await
+                    .unwrap_or_else(|payload| Err(ListenerPanic::from_payload(payload).into()))
+            }
+            Callback::Async(callback) => callback(event_arg).await,
         }
     }
 }
@@ -1,8 +1,51 @@
-use super::{emitter::EventHubEmitter, listener::Listener, registry::ListenerRegistry};
-use crate::{EventEmitter, EventManager};
-use anyhow::{Result, Error};
+use super::{
+    context::EventContext,
+    correlation::{Cause, CausedFuture},
+    emitter::EventHubEmitter,
+    journal::EventJournal,
+    listener::{GateGuard, Listener},
+    policy::{ErrorPolicy, Failures, HubSettings, PanicPolicy},
+    registry::ListenerRegistry,
+    replay::{ReplayBuffers, ReplayWindow},
+    stream::EventStream,
+    wait::EventWait,
+};
+use crate::{
+    registry::{Recoverable, DEFAULT_PRIORITY},
+    EmitixError,
+    Envelope,
+    EventMetadata,
+    Event,
+    EventEmitter,
+    EventKey,
+    EventManager,
+    HubHealth,
+    ListenerFailure,
+    ListenerPanic,
+    Result,
+    Subscription,
+};
+use futures::channel::{mpsc, oneshot};
 use uuid::Uuid;
-use std::sync::{Arc, RwLock};
+use std::{
+    collections::HashMap,
+    future::Future,
+    num::NonZeroUsize,
+    ops::ControlFlow,
+    slice,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::sync_channel,
+        Arc,
+        RwLock,
+        RwLockReadGuard,
+        RwLockWriteGuard,
+    },
+    time::{Duration, SystemTime},
+};
+
+/// The current values of the sticky event kinds, with the metadata of their emission in envelope mode.
+type StickyValues<T, K> = HashMap<K, (T, Option<EventMetadata>)>;
 
 /// `EventHub` is a thread-safe structure for managing events.
 ///
@@ -10,6 +53,7 @@ use std::sync::{Arc, RwLock};
 /// - Allows adding event listeners.
 /// - Emits events to all registered listeners.
 /// - Uses `Arc` and `Mutex` to ensure thread safety.
+/// - Identifies the event kinds with `String` keys by default, or with any [`EventKey`](crate::EventKey).
 ///
 /// # Example Usage
 /// ```rust
@@ -33,165 +77,2198 @@ use std::sync::{Arc, RwLock};
 /// - The structure can be cloned and shared across multiple threads using `Arc`.
 /// - Listeners must be thread-safe functions (`Send` and `Sync`).
 #[derive(Clone)]
-pub struct EventHub<T: Clone + Send + Sync + 'static = ()> {
-    registry: Arc<RwLock<ListenerRegistry<T>>>,
+pub struct EventHub<T: Clone + Send + Sync + 'static = (), K: EventKey = String> {
+    registry: Arc<RwLock<ListenerRegistry<T, K>>>,
+    settings: Arc<RwLock<HubSettings>>,
+    sticky:   Arc<RwLock<StickyValues<T, K>>>,
+    replay:   Arc<RwLock<ReplayBuffers<T, K>>>,
+    journal:  Arc<RwLock<Option<EventJournal<T, K>>>>,
+    sequence: Arc<AtomicU64>,
 }
 
-impl<T: Clone + Send + Sync + 'static> EventHub<T> {
-    /// Creates a new instance of `EventHub`.
-    ///
-    /// # Returns
-    /// A new, empty instance of `EventHub` ready to register listeners.
-    pub fn new() -> Self {
-        Self::default()
+impl<T: Clone + Send + Sync + 'static, K: EventKey> EventHub<T, K> {
+    /// Creates a new instance of `EventHub`.
+    ///
+    /// # Returns
+    /// A new, empty instance of `EventHub` ready to register listeners.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emits an event to all registered listeners.
+    ///
+    /// The asynchronous listeners are skipped, each one being reported as a failure
+    /// whose source is an [`AsyncListenerSkipped`](crate::AsyncListenerSkipped) error.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event being emitted.
+    /// - `event_arg`: The event argument that will be passed to each listener.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the event was successfully emitted.
+    /// - `Err(EmitixError::LockPoisoned)` if access to the `Mutex` failed.
+    /// - `Err(EmitixError::ListenerFailures)` if some listeners returned an error, or were asynchronous.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::EventHub;
+    ///
+    /// let manager = EventHub::default();
+    /// manager
+    ///     .emit("Events You Like", String::from("Test Event"))
+    ///     .unwrap();
+    /// ```
+    pub fn emit<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q, event_arg: T) -> Result<()> {
+        self.emit_from(None, &event_kind.to_owned(), event_arg)
+    }
+
+    /// Emits an event which can be cancelled by the listeners.
+    ///
+    /// Each listener added with [`EventHub::add_contextual_listener`] can cancel the event
+    /// with [`EventContext::prevent_default`], and stop its propagation to the next listeners
+    /// with [`EventContext::stop_propagation`].
+    /// With priorities, a listener can veto the event before the other listeners are called.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event being emitted.
+    /// - `event_arg`: The event argument that will be passed to each listener.
+    ///
+    /// # Returns
+    /// - `Ok(bool)` indicating whether a listener cancelled the event.
+    /// - `Err(EmitixError::LockPoisoned)` if access to the `Mutex` failed.
+    /// - `Err(EmitixError::ListenerFailures)` if some listeners returned an error.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::{EventContext, EventHub};
+    /// use emitix::EventManager;
+    /// use std::sync::{
+    ///     atomic::{AtomicBool, Ordering},
+    ///     Arc,
+    /// };
+    ///
+    /// let manager = EventHub::default();
+    /// let deleted = Arc::new(AtomicBool::new(false));
+    ///
+    /// manager
+    ///     .add_contextual_listener_with_priority(
+    ///         "Delete File",
+    ///         100,
+    ///         |path: String, context: &EventContext| {
+    ///             if path.starts_with("/etc") {
+    ///                 context.prevent_default();
+    ///                 context.stop_propagation();
+    ///             }
+    ///         },
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let flag = Arc::clone(&deleted);
+    /// manager
+    ///     .add_listener("Delete File", move |_: String| {
+    ///         flag.store(true, Ordering::SeqCst)
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert!(
+    ///     manager
+    ///         .emit_cancellable("Delete File", String::from("/etc/hosts"))
+    ///         .unwrap()
+    /// );
+    /// assert!(!deleted.load(Ordering::SeqCst));
+    ///
+    /// assert!(
+    ///     !manager
+    ///         .emit_cancellable("Delete File", String::from("/tmp/file"))
+    ///         .unwrap()
+    /// );
+    /// assert!(deleted.load(Ordering::SeqCst));
+    /// ```
+    pub fn emit_cancellable<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q, event_arg: T) -> Result<bool> {
+        let settings = self.settings()?;
+        let event_kinds = [event_kind.to_owned()];
+        let context = self.context(&settings, Some(&event_kinds), None);
+
+        self.dispatch(
+            self.emission(Some(&event_kinds), &event_arg, &context, settings.bubbling)?,
+            event_arg,
+            &settings,
+            &context,
+        )?;
+        Ok(context.is_default_prevented())
+    }
+
+    /// Emits an event to all registered listeners, handling the listener failures with a specific policy.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event being emitted.
+    /// - `event_arg`: The event argument that will be passed to each listener.
+    /// - `policy`: The policy used instead of the policy of the hub.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the event was successfully emitted, or if the policy discarded the failures.
+    /// - `Err(EmitixError::LockPoisoned)` if access to the `Mutex` failed.
+    /// - `Err(EmitixError::ListenerFailures)` if some listeners returned an error.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::{ErrorPolicy, EventHub};
+    ///
+    /// let manager = EventHub::default();
+    /// manager
+    ///     .emit_with_policy(
+    ///         "Events You Like",
+    ///         String::from("Test Event"),
+    ///         &ErrorPolicy::StopOnFirstError,
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn emit_with_policy<Q: ?Sized + ToOwned<Owned = K>>(
+        &self,
+        event_kind: &Q,
+        event_arg: T,
+        policy: &ErrorPolicy,
+    ) -> Result<()> {
+        let settings = HubSettings {
+            error_policy: policy.clone(),
+            ..self.settings()?
+        };
+
+        let event_kinds = [event_kind.to_owned()];
+        let context = self.context(&settings, Some(&event_kinds), None);
+
+        self.dispatch(
+            self.emission(Some(&event_kinds), &event_arg, &context, settings.bubbling)?,
+            event_arg,
+            &settings,
+            &context,
+        )
+    }
+
+    /// Emits an event to all registered listeners and awaits the asynchronous ones.
+    ///
+    /// Listeners are called one after the other, each future being awaited before the next listener runs.
+    /// Synchronous listeners are called inline.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event being emitted.
+    /// - `event_arg`: The event argument that will be passed to each listener.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the event was successfully emitted.
+    /// - `Err(EmitixError::LockPoisoned)` if access to the `Mutex` failed.
+    /// - `Err(EmitixError::ListenerFailures)` if some listeners returned an error.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::EventHub;
+    /// use futures::executor::block_on;
+    ///
+    /// let manager = EventHub::default();
+    ///
+    /// manager
+    ///     .add_async_listener("Events You Like", |event: String| async move {
+    ///         println!("Event received: {}", event);
+    ///
+    ///         Ok(())
+    ///     })
+    ///     .unwrap();
+    ///
+    /// block_on(manager.emit_async("Events You Like", String::from("Test Event"))).unwrap();
+    /// ```
+    pub async fn emit_async<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q, event_arg: T) -> Result<()> {
+        let settings = self.settings()?;
+        let catch_panics = settings.panic_policy.catches_panics();
+        let event_kinds = [event_kind.to_owned()];
+        let context = self.context(&settings, Some(&event_kinds), None);
+        let listeners = self.emission(Some(&event_kinds), &event_arg, &context, settings.bubbling)?;
+
+        // The listeners are polled as listeners of the emission, so their emissions are correlated with it.
+        CausedFuture::new(context.metadata().map(Cause::of), async {
+            let mut failures = Failures::new(&settings.error_policy);
+            let mut panicked = vec![];
+
+            for (id, kind, listener) in listeners {
+                if context.is_propagation_stopped() {
+                    break;
+                }
+
+                if listener.is_quarantined() {
+                    continue;
+                }
+
+                if let Err(source) = listener.call_async(event_arg.clone(), &context, catch_panics).await {
+                    if source.is::<ListenerPanic>() {
+                        panicked.push(id);
+                    }
+
+                    let failure = ListenerFailure {
+                        id,
+                        kind: kind.name(),
+                        source,
+                    };
+
+                    if failures.push(failure).is_break() {
+                        break;
+                    }
+                }
+            }
+
+            self.isolate(panicked, settings.panic_policy);
+            failures.finish()
+        })
+        .await
+    }
+
+    /// Emits the event of an envelope to all registered listeners, like [`EventHub::emit`].
+    ///
+    /// The emission keeps the metadata of the envelope, except its sequence number,
+    /// so an event coming from another process keeps its identifier, its timestamp and its correlation.
+    /// In envelope mode, the emission gets the next sequence number of the hub, otherwise it has none.
+    ///
+    /// # Arguments
+    /// - `envelope`: The event with its event kind and its metadata.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the event was successfully emitted.
+    /// - `Err(EmitixError::LockPoisoned)` if access to the `Mutex` failed.
+    /// - `Err(EmitixError::ListenerFailures)` if some listeners returned an error.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::{event_hub::EventHub, Envelope, EventMetadata};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let manager = EventHub::default();
+    /// let received = Arc::new(Mutex::new(Vec::new()));
+    /// let envelopes = Arc::clone(&received);
+    ///
+    /// manager
+    ///     .add_envelope_listener("user.created", move |envelope: Envelope<String>| {
+    ///         envelopes.lock().unwrap().push(envelope)
+    ///     })
+    ///     .unwrap();
+    ///
+    /// let cause = Envelope::new(String::from("user.signed_up"), String::from("Alice"));
+    /// let envelope = Envelope::with_metadata(
+    ///     EventMetadata {
+    ///         seq: Some(41),
+    ///         causation_id: Some(cause.id),
+    ///         correlation_id: cause.correlation_id,
+    ///         ..Envelope::new(String::from("user.created"), ()).metadata()
+    ///     },
+    ///     String::from("user.created"),
+    ///     String::from("Alice"),
+    /// );
+    ///
+    /// manager.emit_envelope(envelope.clone()).unwrap();
+    ///
+    /// let received = received.lock().unwrap();
+    /// assert_eq!(received[0].id, envelope.id);
+    /// assert_eq!(received[0].timestamp, envelope.timestamp);
+    /// assert_eq!(received[0].correlation_id, cause.correlation_id);
+    /// assert_eq!(received[0].causation_id, Some(cause.id));
+    /// assert_eq!(received[0].seq, None);
+    /// ```
+    pub fn emit_envelope(&self, envelope: Envelope<T, K>) -> Result<()> {
+        let settings = self.settings()?;
+        let metadata = envelope.metadata();
+        let (event_kind, event_arg) = envelope.into_parts();
+        let metadata = EventMetadata {
+            seq: settings.envelopes.then(|| self.sequence.fetch_add(1, Ordering::SeqCst)),
+            ..metadata
+        };
+        let context = EventContext::stamped(metadata, Some(event_kind.clone()));
+
+        self.dispatch(
+            self.emission(
+                Some(slice::from_ref(&event_kind)),
+                &event_arg,
+                &context,
+                settings.bubbling,
+            )?,
+            event_arg,
+            &settings,
+            &context,
+        )
+    }
+
+    /// Emits a sticky event, whose value is kept as the current value of the event kind.
+    ///
+    /// The event is emitted like with [`EventHub::emit`], and its value replaces the previous one.
+    /// The listeners added later with [`EventHub::add_listener_replay`] receive it as soon as they are added.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event being emitted.
+    /// - `event_arg`: The event argument that will be passed to each listener and kept.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the event was successfully emitted.
+    /// - `Err(EmitixError::LockPoisoned)` if access to the `Mutex` failed.
+    /// - `Err(EmitixError::ListenerFailures)` if some listeners returned an error.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::EventHub;
+    ///
+    /// let manager = EventHub::default();
+    /// manager
+    ///     .emit_sticky("connection.state", String::from("connected"))
+    ///     .unwrap();
+    ///
+    /// manager
+    ///     .add_listener_replay("connection.state", |state: String| {
+    ///         println!("Connection state: {state}")
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     manager.sticky_value("connection.state").unwrap(),
+    ///     Some(String::from("connected"))
+    /// );
+    /// ```
+    pub fn emit_sticky<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q, event_arg: T) -> Result<()> {
+        let settings = self.settings()?;
+        let event_kind = event_kind.to_owned();
+
+        let context = self.context(&settings, Some(slice::from_ref(&event_kind)), None);
+
+        // The event is journaled before the value is stored, so the readers of the value don't wait for the journal.
+        self.journal_emission(slice::from_ref(&event_kind), &event_arg)?;
+
+        // The listeners are taken while the value is stored, so a listener added meanwhile receives it once.
+        let listeners = {
+            let mut sticky = self.write_sticky()?;
+
+            sticky.insert(event_kind.clone(), (event_arg.clone(), context.metadata().copied()));
+            self.recorded_snapshot(slice::from_ref(&event_kind), &event_arg, &context, settings.bubbling)?
+        };
+
+        self.dispatch(listeners, event_arg, &settings, &context)
+    }
+
+    /// Adds an event listener, and calls it right away with the current values of the sticky events it listens for.
+    ///
+    /// When the event kind is a pattern, or when the events bubble,
+    /// the listener receives the current value of each sticky event kind it is reached by, in no particular order.
+    /// The events emitted meanwhile by the other threads are passed to the listener once it has received
+    /// the current values, their emissions wait until then.
+    /// In envelope mode, the current values keep the metadata of their emission.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event this listener is for.
+    /// - `listener`: A function or closure that will be called when the event is emitted.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the listener.
+    /// - `Err(EmitixError::LockPoisoned)` if access to the `Mutex` failed.
+    /// - `Err(EmitixError::ListenerFailures)` if the listener failed with a current value,
+    ///   it stays registered and its identifier is in the failures.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::EventHub;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let manager = EventHub::default();
+    /// let config = Arc::new(Mutex::new(String::new()));
+    ///
+    /// manager
+    ///     .emit_sticky("config.database", String::from("postgres://localhost"))
+    ///     .unwrap();
+    ///
+    /// let current = Arc::clone(&config);
+    /// manager
+    ///     .add_listener_replay("config.*", move |value: String| {
+    ///         *current.lock().unwrap() = value
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(*config.lock().unwrap(), "postgres://localhost");
+    /// ```
+    pub fn add_listener_replay<Q: ?Sized + ToOwned<Owned = K>, F: FnMut(T) + Send + Sync + 'static>(
+        &self,
+        event_kind: &Q,
+        listener: F,
+    ) -> Result<Uuid> {
+        let settings = self.settings()?;
+        let event_kind = event_kind.to_owned();
+
+        // The values are taken while the listener is added, so a value stored meanwhile is received once,
+        // and the emissions of the other threads wait at the gate until the values are received.
+        let (listener_id, listener, _gate, values) = {
+            let sticky = self.read_sticky()?;
+            let (listener_id, listener, gate) = self.register_replayed_listener(&event_kind, listener);
+            let values = sticky
+                .iter()
+                .filter(|(sticky_kind, _)| Self::reaches(&event_kind, sticky_kind, settings.bubbling))
+                .map(|(sticky_kind, (value, metadata))| (sticky_kind.clone(), *metadata, value.clone()))
+                .collect::<Vec<_>>();
+
+            (listener_id, listener, gate, values)
+        };
+
+        self.replay_to_listener(listener_id, &listener, values, &settings)?;
+
+        Ok(listener_id)
+    }
+
+    /// Returns the current value of a sticky event kind.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event whose value is returned.
+    ///
+    /// # Returns
+    /// - `Ok(Some(T))` with the value of the last sticky event of this kind.
+    /// - `Ok(None)` if no sticky event of this kind was emitted since the last clearing.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn sticky_value<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q) -> Result<Option<T>> {
+        Ok(self
+            .read_sticky()?
+            .get(&event_kind.to_owned())
+            .map(|(value, _)| value.clone()))
+    }
+
+    /// Clears the current value of a sticky event kind, the listeners added later don't receive it anymore.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event whose value is cleared.
+    ///
+    /// # Returns
+    /// - `Ok(Option<T>)` with the cleared value, if there was one.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::EventHub;
+    ///
+    /// let manager = EventHub::default();
+    /// manager.emit_sticky("connection.state", 1u8).unwrap();
+    ///
+    /// assert_eq!(manager.clear_sticky("connection.state").unwrap(), Some(1));
+    /// assert_eq!(manager.sticky_value("connection.state").unwrap(), None);
+    /// ```
+    pub fn clear_sticky<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q) -> Result<Option<T>> {
+        Ok(self
+            .write_sticky()?
+            .remove(&event_kind.to_owned())
+            .map(|(value, _)| value))
+    }
+
+    /// Sets the replay window of an event kind, whose events are recorded for the listeners added with history.
+    ///
+    /// When the event kind had a window already, the recorded events which still fit in the new window are kept.
+    ///
+    /// A window records the events emitted for its exact event kind only: a pattern can't have a window,
+    /// and the events which bubble up from the child kinds are not recorded in the window of their parent.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event to record, which must not be a pattern.
+    /// - `window`: The window of the recorded events.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the window was successfully set.
+    /// - `Err(EmitixError::InvalidArgument)` if the event kind is a pattern, no window is set.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::{EventHub, ReplayWindow};
+    ///
+    /// let manager = EventHub::default();
+    /// manager.set_bubbling(true).unwrap();
+    /// manager
+    ///     .set_replay_window("orders", ReplayWindow::last(10))
+    ///     .unwrap();
+    ///
+    /// assert!(
+    ///     manager
+    ///         .set_replay_window("orders.*", ReplayWindow::last(10))
+    ///         .is_err()
+    /// );
+    ///
+    /// manager.emit("orders", 1).unwrap();
+    /// manager.emit("orders.created", 2).unwrap();
+    ///
+    /// assert_eq!(manager.history("orders").unwrap(), vec![1]);
+    /// ```
+    pub fn set_replay_window<Q: ?Sized + ToOwned<Owned = K>>(
+        &self,
+        event_kind: &Q,
+        window: ReplayWindow,
+    ) -> Result<()> {
+        let event_kind = event_kind.to_owned();
+
+        if event_kind.is_pattern() {
+            return Err(EmitixError::invalid_argument("event_kind", "must not be a pattern"));
+        }
+
+        self.write_replay()?.set_window(event_kind, window);
+
+        Ok(())
+    }
+
+    /// Returns the replay window of an event kind.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event whose window is returned.
+    ///
+    /// # Returns
+    /// - `Ok(Option<ReplayWindow>)` with the window of the event kind, if it has one.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn replay_window<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q) -> Result<Option<ReplayWindow>> {
+        Ok(self.read_replay()?.window(&event_kind.to_owned()))
+    }
+
+    /// Removes the replay window of an event kind, with its recorded events.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event whose window is removed.
+    ///
+    /// # Returns
+    /// - `Ok(Option<ReplayWindow>)` with the removed window, if there was one.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn remove_replay_window<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q) -> Result<Option<ReplayWindow>> {
+        Ok(self.write_replay()?.remove_window(&event_kind.to_owned()))
+    }
+
+    /// Returns the events recorded in the replay window of an event kind, from the oldest to the newest.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event whose recorded events are returned.
+    ///
+    /// # Returns
+    /// - `Ok(Vec<T>)` with the recorded events, which is empty if the event kind has no window.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::{EventHub, ReplayWindow};
+    ///
+    /// let manager = EventHub::default();
+    /// manager
+    ///     .set_replay_window("sensor.temperature", ReplayWindow::last(2))
+    ///     .unwrap();
+    ///
+    /// for temperature in [18, 19, 21] {
+    ///     manager.emit("sensor.temperature", temperature).unwrap();
+    /// }
+    ///
+    /// assert_eq!(manager.history("sensor.temperature").unwrap(), vec![19, 21]);
+    /// ```
+    pub fn history<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q) -> Result<Vec<T>> {
+        let event_kind = event_kind.to_owned();
+
+        Ok(self
+            .read_replay()?
+            .history(|recorded_kind| *recorded_kind == event_kind)
+            .into_iter()
+            .map(|(_, _, event_arg)| event_arg)
+            .collect())
+    }
+
+    /// Adds an event listener, and calls it right away with the events recorded in the replay windows
+    /// of the event kinds it listens for, before it receives the next events.
+    ///
+    /// When the event kind is a pattern, or when the events bubble, the listener receives the recorded events
+    /// of each event kind it is reached by, in emission order.
+    /// The events emitted meanwhile by the other threads are passed to the listener once it has received
+    /// the recorded events, their emissions wait until then.
+    /// In envelope mode, the recorded events keep the metadata of their emission.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event this listener is for.
+    /// - `listener`: A function or closure that will be called when the event is emitted.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the listener.
+    /// - `Err(EmitixError::LockPoisoned)` if access to the `Mutex` failed.
+    /// - `Err(EmitixError::ListenerFailures)` if the listener failed with a recorded event,
+    ///   it stays registered and its identifier is in the failures.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::{EventHub, ReplayWindow};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let manager = EventHub::default();
+    /// let lines = Arc::new(Mutex::new(Vec::new()));
+    ///
+    /// manager
+    ///     .set_replay_window("panel.log", ReplayWindow::last(10))
+    ///     .unwrap();
+    /// manager.emit("panel.log", String::from("Started")).unwrap();
+    ///
+    /// let panel = Arc::clone(&lines);
+    /// manager
+    ///     .add_listener_with_history("panel.log", move |line: String| {
+    ///         panel.lock().unwrap().push(line)
+    ///     })
+    ///     .unwrap();
+    ///
+    /// manager
+    ///     .emit("panel.log", String::from("Connected"))
+    ///     .unwrap();
+    /// assert_eq!(*lines.lock().unwrap(), vec!["Started", "Connected"]);
+    /// ```
+    ///
+    /// An event emitted by another thread while the history is replayed is received after the history:
+    /// ```rust
+    /// use emitix::event_hub::{EventHub, ReplayWindow};
+    /// use std::{
+    ///     sync::{mpsc, Arc, Mutex},
+    ///     thread,
+    ///     time::Duration,
+    /// };
+    ///
+    /// let manager = EventHub::default();
+    /// let lines = Arc::new(Mutex::new(Vec::new()));
+    ///
+    /// manager
+    ///     .set_replay_window("panel.log", ReplayWindow::last(10))
+    ///     .unwrap();
+    /// manager.emit("panel.log", String::from("Started")).unwrap();
+    /// manager
+    ///     .emit("panel.log", String::from("Connected"))
+    ///     .unwrap();
+    ///
+    /// let (replaying, replay_started) = mpsc::channel();
+    /// let emitter = manager.clone();
+    /// let live = thread::spawn(move || {
+    ///     replay_started.recv().unwrap();
+    ///     emitter.emit("panel.log", String::from("Ready")).unwrap();
+    /// });
+    ///
+    /// let panel = Arc::clone(&lines);
+    /// manager
+    ///     .add_listener_with_history("panel.log", move |line: String| {
+    ///         if line == "Started" {
+    ///             replaying.send(()).unwrap();
+    ///             thread::sleep(Duration::from_millis(50));
+    ///         }
+    ///
+    ///         panel.lock().unwrap().push(line)
+    ///     })
+    ///     .unwrap();
+    ///
+    /// live.join().unwrap();
+    /// assert_eq!(
+    ///     *lines.lock().unwrap(),
+    ///     vec!["Started", "Connected", "Ready"]
+    /// );
+    /// ```
+    pub fn add_listener_with_history<Q: ?Sized + ToOwned<Owned = K>, F: FnMut(T) + Send + Sync + 'static>(
+        &self,
+        event_kind: &Q,
+        listener: F,
+    ) -> Result<Uuid> {
+        let settings = self.settings()?;
+        let event_kind = event_kind.to_owned();
+
+        // The history is taken while the listener is added, so an event recorded meanwhile is received once,
+        // and the emissions of the other threads wait at the gate until the history is received.
+        let (listener_id, listener, _gate, history) = {
+            let replay = self.read_replay()?;
+            let (listener_id, listener, gate) = self.register_replayed_listener(&event_kind, listener);
+            let history = replay.history(|recorded_kind| Self::reaches(&event_kind, recorded_kind, settings.bubbling));
+
+            (listener_id, listener, gate, history)
+        };
+
+        self.replay_to_listener(listener_id, &listener, history, &settings)?;
+
+        Ok(listener_id)
+    }
+
+    /// Attaches a journal to the hub, which records every event emitted by the hub and by its emitters.
+    ///
+    /// The events are recorded before they are passed to the listeners,
+    /// and an event which can't be recorded is not passed to the listeners.
+    /// An event emitted for several event kinds is recorded once per event kind, and an event emitted
+    /// by a broadcast emitter without event kinds is recorded under each event kind or pattern it reaches.
+    /// The journal replaces the journal previously attached, if any.
+    ///
+    /// # Arguments
+    /// - `journal`: The journal recording the events.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the journal was successfully attached.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::{EventHub, EventJournal};
+    ///
+    /// let manager = EventHub::default();
+    /// let journal = EventJournal::in_memory();
+    ///
+    /// manager.attach_journal(journal.clone()).unwrap();
+    /// manager.emit("user.created", String::from("Alice")).unwrap();
+    ///
+    /// let entries = journal.entries(..).unwrap();
+    /// assert_eq!(entries[0].sequence, 0);
+    /// assert_eq!(entries[0].payload, "Alice");
+    /// ```
+    ///
+    /// A broadcast to all the event kinds is recorded under the event kinds of its listeners, without their patterns:
+    /// ```rust
+    /// use emitix::{
+    ///     event_hub::{EventHub, EventJournal},
+    ///     EventManager,
+    /// };
+    ///
+    /// let manager = EventHub::default();
+    /// let journal = EventJournal::in_memory();
+    ///
+    /// manager.attach_journal(journal.clone()).unwrap();
+    /// manager
+    ///     .add_listener("user.created", |_: String| ())
+    ///     .unwrap();
+    /// manager
+    ///     .add_listener("user.deleted", |_: String| ())
+    ///     .unwrap();
+    /// manager.add_listener("user.*", |_: String| ()).unwrap();
+    ///
+    /// let broadcaster = manager.new_broadcast_emitter::<str>(&[]);
+    /// broadcaster.emit(String::from("Shutdown")).unwrap();
+    ///
+    /// let entries = journal.entries(..).unwrap();
+    /// assert_eq!(entries.len(), 2);
+    /// assert_eq!(entries[0].kind, "user.created");
+    /// assert_eq!(entries[1].kind, "user.deleted");
+    /// assert!(entries.iter().all(|entry| entry.payload == "Shutdown"));
+    /// ```
+    pub fn attach_journal(&self, journal: EventJournal<T, K>) -> Result<()> {
+        let mut attached = self
+            .journal
+            .write()
+            .map_err(|_| EmitixError::lock_poisoned("event hub journal"))?;
+
+        *attached = Some(journal);
+        Ok(())
+    }
+
+    /// Detaches the journal from the hub, the next events are not recorded anymore.
+    ///
+    /// # Returns
+    /// - `Ok(Option<EventJournal<T, K>>)` with the detached journal, if there was one.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn detach_journal(&self) -> Result<Option<EventJournal<T, K>>> {
+        let mut attached = self
+            .journal
+            .write()
+            .map_err(|_| EmitixError::lock_poisoned("event hub journal"))?;
+
+        Ok(attached.take())
+    }
+
+    /// Adds an event listener receiving the events in envelopes, with their event kind and their metadata.
+    ///
+    /// The metadata are assigned by the hub in envelope mode only, see [`EventHub::set_envelope_mode`].
+    /// Otherwise, the envelopes have no sequence number and no causation, and they get an identifier
+    /// and a timestamp created once per emission, so the envelope listeners of an emission receive the same ones,
+    /// except for the events emitted with [`EventHub::emit_envelope`], which keep the metadata of their envelope.
+    ///
+    /// The envelopes carry the emitted event kind, even when the listener is added for a pattern or for a parent
+    /// of the event kind. Only the emissions for several event kinds give the event kind of the listener instead.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event this listener is for.
+    /// - `listener`: A function or closure that will be called with the envelope of each emitted event.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the listener.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::{event_hub::EventHub, Envelope};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let manager = EventHub::default();
+    /// let envelopes = Arc::new(Mutex::new(Vec::new()));
+    ///
+    /// for _ in 0..2 {
+    ///     let received = Arc::clone(&envelopes);
+    ///     manager
+    ///         .add_envelope_listener("user.created", move |envelope: Envelope<String>| {
+    ///             received.lock().unwrap().push(envelope)
+    ///         })
+    ///         .unwrap();
+    /// }
+    ///
+    /// manager.emit("user.created", String::from("Alice")).unwrap();
+    ///
+    /// let envelopes = envelopes.lock().unwrap();
+    /// assert_eq!(envelopes[0].seq, None);
+    /// assert_eq!(envelopes[0].id, envelopes[1].id);
+    /// assert_eq!(envelopes[0].payload, "Alice");
+    /// ```
+    ///
+    /// The listeners added for a pattern or for a parent kind receive the emitted event kind:
+    /// ```rust
+    /// use emitix::{event_hub::EventHub, Envelope};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let manager = EventHub::default();
+    /// let kinds = Arc::new(Mutex::new(Vec::new()));
+    ///
+    /// manager.set_bubbling(true).unwrap();
+    /// for event_kind in ["user.*", "user"] {
+    ///     let received = Arc::clone(&kinds);
+    ///     manager
+    ///         .add_envelope_listener(event_kind, move |envelope: Envelope<String>| {
+    ///             received.lock().unwrap().push(envelope.kind)
+    ///         })
+    ///         .unwrap();
+    /// }
+    ///
+    /// manager.emit("user.created", String::from("Alice")).unwrap();
+    ///
+    /// assert_eq!(*kinds.lock().unwrap(), ["user.created", "user.created"]);
+    /// ```
+    pub fn add_envelope_listener<Q: ?Sized + ToOwned<Owned = K>, F>(
+        &self,
+        event_kind: &Q,
+        mut listener: F,
+    ) -> Result<Uuid>
+    where
+        F: FnMut(Envelope<T, K>) + Send + Sync + 'static, {
+        let event_kind = event_kind.to_owned();
+        let registered_kind = event_kind.clone();
+        let mut registry = self.write_registry();
+
+        Ok(registry.register_contextual_listener(
+            &event_kind,
+            DEFAULT_PRIORITY,
+            move |event_arg, context: &EventContext| {
+                // The emissions for several event kinds don't tell which one reached the listener.
+                let kind = context.event_kind::<K>().unwrap_or(&registered_kind).clone();
+                listener(Envelope::with_metadata(context.envelope_metadata(), kind, event_arg))
+            },
+        ))
+    }
+
+    /// Adds an asynchronous event listener.
+    ///
+    /// The listener is awaited by [`EventHub::emit_async`].
+    /// When the event is emitted synchronously (with [`EventHub::emit`] or an emitter), the listener is skipped,
+    /// as the emitting thread would be blocked on its future, and the emission reports
+    /// an [`AsyncListenerSkipped`](crate::AsyncListenerSkipped) failure for it.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event this listener is for.
+    /// - `listener`: A function or closure returning a future that resolves to `anyhow::Result<()>`.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the listener.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::{event_hub::EventHub, EventManager};
+    ///
+    /// let manager = EventHub::default();
+    /// let listener_id = manager
+    ///     .add_async_listener("Events You Like", |event: String| async move {
+    ///         println!("Event received: {}", event);
+    ///
+    ///         Ok(())
+    ///     })
+    ///     .unwrap();
+    ///
+    /// manager.remove_listener(listener_id).unwrap();
+    /// ```
+    pub fn add_async_listener<Q: ?Sized + ToOwned<Owned = K>, F, Fut>(
+        &self,
+        event_kind: &Q,
+        listener: F,
+    ) -> Result<Uuid>
+    where
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static, {
+        let mut registry = self.write_registry();
+
+        Ok(registry.register_async_listener(&event_kind.to_owned(), listener))
+    }
+
+    /// Adds an event listener which receives the context of the emission.
+    ///
+    /// The listener can stop the propagation of the event to the next listeners,
+    /// or cancel the event, as reported by [`EventHub::emit_cancellable`].
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event this listener is for.
+    /// - `listener`: A function or closure that will be called with the event argument and the context.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the listener.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::{EventContext, EventHub};
+    ///
+    /// let manager = EventHub::default();
+    /// manager
+    ///     .add_contextual_listener("Events You Like", |_: String, context: &EventContext| {
+    ///         context.stop_propagation();
+    ///     })
+    ///     .unwrap();
+    ///
+    /// manager
+    ///     .emit("Events You Like", String::from("Test Event"))
+    ///     .unwrap();
+    /// ```
+    pub fn add_contextual_listener<Q: ?Sized + ToOwned<Owned = K>, F>(
+        &self,
+        event_kind: &Q,
+        listener: F,
+    ) -> Result<Uuid>
+    where
+        F: FnMut(T, &EventContext) + Send + Sync + 'static, {
+        self.add_contextual_listener_with_priority(event_kind, DEFAULT_PRIORITY, listener)
+    }
+
+    /// Adds an event listener with a priority which receives the context of the emission.
+    ///
+    /// Listeners with a higher priority are called first, so they can veto the event
+    /// before the listeners with a lower priority are called.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event this listener is for.
+    /// - `priority`: The priority of the listener.
+    /// - `listener`: A function or closure that will be called with the event argument and the context.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the listener.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn add_contextual_listener_with_priority<Q: ?Sized + ToOwned<Owned = K>, F>(
+        &self,
+        event_kind: &Q,
+        priority: i32,
+        listener: F,
+    ) -> Result<Uuid>
+    where
+        F: FnMut(T, &EventContext) + Send + Sync + 'static, {
+        let mut registry = self.write_registry();
+
+        Ok(registry.register_contextual_listener(&event_kind.to_owned(), priority, listener))
+    }
+
+    /// Subscribes to an event kind as a stream of events.
+    ///
+    /// The events are buffered in a bounded channel; when the buffer is full,
+    /// the new events are dropped until the stream is polled again.
+    /// The stream ends when its listener is removed from the hub,
+    /// and the listener is removed when the stream is dropped.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event to subscribe to.
+    /// - `capacity`: The number of events that can be buffered before being consumed.
+    ///
+    /// # Returns
+    /// - `Ok(EventStream<T>)` which yields the events emitted for the specified event kind.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::EventHub;
+    /// use futures::{executor::block_on, StreamExt};
+    ///
+    /// let manager = EventHub::default();
+    /// let mut stream = manager.subscribe_stream("Events You Like", 16).unwrap();
+    ///
+    /// manager
+    ///     .emit("Events You Like", String::from("Test Event"))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(block_on(stream.next()), Some(String::from("Test Event")));
+    /// ```
+    pub fn subscribe_stream<Q: ?Sized + ToOwned<Owned = K>>(
+        &self,
+        event_kind: &Q,
+        capacity: usize,
+    ) -> Result<EventStream<T>> {
+        let (mut sender, receiver) = mpsc::channel(capacity);
+        let mut registry = self.write_registry();
+
+        let listener_id = registry.register_listener(&event_kind.to_owned(), move |event_arg| {
+            // A full buffer drops the event, a closed one means the stream is being dropped.
+            let _ = sender.try_send(event_arg);
+        });
+
+        Ok(EventStream::new(receiver, self.subscription(listener_id)))
+    }
+
+    /// Waits for the next event of a specific kind.
+    ///
+    /// The hub starts listening when this method is called, not when the future is first polled,
+    /// so an event emitted before awaiting the future is not missed.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event to wait for.
+    ///
+    /// # Returns
+    /// - `Ok(EventWait<T>)` which is a future resolving with the event argument,
+    ///   or with `EmitixError::NoListeners` if the listener is removed before the event occurs.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::EventHub;
+    /// use futures::executor::block_on;
+    ///
+    /// let manager = EventHub::default();
+    /// let ready = manager.wait_for("Ready").unwrap();
+    ///
+    /// manager.emit("Ready", String::from("Started")).unwrap();
+    /// assert_eq!(block_on(ready).unwrap(), "Started");
+    /// ```
+    pub fn wait_for<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q) -> Result<EventWait<T>> {
+        self.wait_for_matching(event_kind, |_| true)
+    }
+
+    /// Waits for the next event of a specific kind whose argument matches a predicate.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event to wait for.
+    /// - `predicate`: A function which returns `true` for the awaited event argument.
+    ///
+    /// # Returns
+    /// - `Ok(EventWait<T>)` which is a future resolving with the first matching event argument,
+    ///   or with `EmitixError::NoListeners` if the listener is removed before the event occurs.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::EventHub;
+    /// use futures::executor::block_on;
+    ///
+    /// let manager = EventHub::default();
+    /// let ready = manager
+    ///     .wait_for_matching("Status", |status: &u32| *status == 200)
+    ///     .unwrap();
+    ///
+    /// manager.emit("Status", 404).unwrap();
+    /// manager.emit("Status", 200).unwrap();
+    /// assert_eq!(block_on(ready).unwrap(), 200);
+    /// ```
+    pub fn wait_for_matching<Q: ?Sized + ToOwned<Owned = K>, P>(
+        &self,
+        event_kind: &Q,
+        predicate: P,
+    ) -> Result<EventWait<T>>
+    where
+        P: Fn(&T) -> bool + Send + Sync + 'static, {
+        let event_kind = event_kind.to_owned();
+        let (sender, receiver) = oneshot::channel();
+        let mut sender = Some(sender);
+        let mut registry = self.write_registry();
+
+        let listener_id =
+            registry.register_expiring_listener(Arc::downgrade(&self.registry), &event_kind, move |event_arg| {
+                if !predicate(&event_arg) {
+                    return false;
+                }
+
+                if let Some(sender) = sender.take() {
+                    let _ = sender.send(event_arg);
+                }
+
+                true
+            });
+
+        Ok(EventWait::new(
+            receiver,
+            self.subscription(listener_id),
+            event_kind.name(),
+        ))
+    }
+
+    /// Blocks the current thread until the next event of a specific kind, or until the timeout expires.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event to wait for.
+    /// - `timeout`: The maximum duration to wait for.
+    ///
+    /// # Returns
+    /// - `Ok(Some(T))` with the event argument if the event occurred in time.
+    /// - `Ok(None)` if the timeout expired, or if the listener was removed before the event occurred.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::EventHub;
+    /// use std::time::Duration;
+    ///
+    /// let manager = EventHub::<String>::default();
+    /// let received = manager
+    ///     .recv_timeout("Ready", Duration::from_millis(10))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(received, None);
+    /// ```
+    pub fn recv_timeout<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q, timeout: Duration) -> Result<Option<T>> {
+        let (sender, receiver) = sync_channel(1);
+        let listener_id = {
+            let mut registry = self.write_registry();
+
+            registry.register_expiring_listener(
+                Arc::downgrade(&self.registry),
+                &event_kind.to_owned(),
+                move |event_arg| {
+                    let _ = sender.try_send(event_arg);
+
+                    true
+                },
+            )
+        };
+
+        let received = receiver.recv_timeout(timeout).ok();
+
+        self.subscription(listener_id).unsubscribe();
+        Ok(received)
+    }
+
+    /// Adds a responder, which is a listener returning a value to the requests of an event kind.
+    ///
+    /// Responders are called by [`EventHub::request`], [`EventHub::request_first`]
+    /// and [`EventHub::request_fold`], not by the emissions.
+    /// Apart from that, they are handled like the other listeners:
+    /// - they can be registered for patterns, and they are reached by the requests of the child kinds
+    ///   when the events bubble;
+    /// - they are called by decreasing priority, then in registration order;
+    /// - they are counted by [`EventHub::has_responders`] and [`EventHub::responders_count`],
+    ///   not by `has_listeners`, `listeners_count` and `list_event_kinds`, which count what an emission reaches;
+    /// - they are removed with their identifier, by `remove_listeners_by_kind` and by `clear_listeners`;
+    /// - they are removed or quarantined after a panic, according to the panic policy.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of request this responder answers.
+    /// - `responder`: A function or closure returning the response to the request argument.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the responder.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::{
+    ///     event_hub::{EventHub, PanicPolicy},
+    ///     EventManager,
+    /// };
+    ///
+    /// let manager = EventHub::default();
+    /// manager
+    ///     .set_panic_policy(PanicPolicy::CatchAndRemove)
+    ///     .unwrap();
+    ///
+    /// let responder_id = manager
+    ///     .add_responder("plugins.editor", |_: ()| String::from("Markdown"))
+    ///     .unwrap();
+    /// manager
+    ///     .add_responder("plugins.*", |_: ()| -> String { panic!("Broken plugin") })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(manager.responders_count("plugins.editor").unwrap(), 2);
+    /// assert!(!manager.has_listeners("plugins.editor").unwrap());
+    /// assert!(manager.request::<String>("plugins.editor", ()).is_err());
+    /// assert_eq!(manager.responders_count("plugins.editor").unwrap(), 1);
+    ///
+    /// manager.remove_listener(responder_id).unwrap();
+    /// assert!(!manager.has_responders("plugins.editor").unwrap());
+    /// ```
+    pub fn add_responder<Q: ?Sized + ToOwned<Owned = K>, R, F>(&self, event_kind: &Q, responder: F) -> Result<Uuid>
+    where
+        R: 'static,
+        F: Fn(T) -> R + Send + Sync + 'static, {
+        self.add_responder_with_priority(event_kind, DEFAULT_PRIORITY, responder)
+    }
+
+    /// Adds a responder with a priority, the responders with a higher priority being called first.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of request this responder answers.
+    /// - `priority`: The priority of the responder.
+    /// - `responder`: A function or closure returning the response to the request argument.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the responder.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::EventHub;
+    ///
+    /// let manager = EventHub::default();
+    /// manager
+    ///     .add_responder("Config Path", |_: ()| "/etc/app.toml")
+    ///     .unwrap();
+    /// manager
+    ///     .add_responder_with_priority("Config Path", 10, |_: ()| "./app.toml")
+    ///     .unwrap();
+    ///
+    /// let path = manager.request_first::<&str>("Config Path", ()).unwrap();
+    /// assert_eq!(path, Some("./app.toml"));
+    /// ```
+    pub fn add_responder_with_priority<Q: ?Sized + ToOwned<Owned = K>, R, F>(
+        &self,
+        event_kind: &Q,
+        priority: i32,
+        responder: F,
+    ) -> Result<Uuid>
+    where
+        R: 'static,
+        F: Fn(T) -> R + Send + Sync + 'static, {
+        let mut registry = self.write_registry();
+
+        Ok(registry.register_responder(&event_kind.to_owned(), priority, responder))
+    }
+
+    /// Checks if there are any responders for a specific event kind.
+    ///
+    /// The responders registered for the patterns matching the event kind are taken into account.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of request to check for responders.
+    ///
+    /// # Returns
+    /// - `Ok(bool)` indicating whether there are responders for the specified event kind.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn has_responders<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q) -> Result<bool> {
+        Ok(self.responders_count(event_kind)? > 0)
+    }
+
+    /// Returns the number of responders for a specific event kind.
+    ///
+    /// It's the number of responders reached by a request of the event kind,
+    /// including the responders registered for the patterns matching it.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of request whose responders count is requested.
+    ///
+    /// # Returns
+    /// - `Ok(usize)` representing the number of responders for the specified event kind.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn responders_count<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q) -> Result<usize> {
+        let registry = self.read_registry();
+
+        Ok(registry.responders_count(&event_kind.to_owned()))
+    }
+
+    /// Sends a request to the responders of an event kind, and collects all their responses.
+    ///
+    /// Only the responders returning values of type `R` are called, the quarantined ones being skipped.
+    /// A panicking responder is reported like a failing listener, and removed or quarantined,
+    /// according to the policies of the hub.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of request being sent.
+    /// - `event_arg`: The request argument that will be passed to each responder.
+    ///
+    /// # Returns
+    /// - `Ok(Vec<R>)` containing the responses in invocation order of the responders.
+    /// - `Err(EmitixError::LockPoisoned)` if access to the `Mutex` failed.
+    /// - `Err(EmitixError::ListenerFailures)` if some responders panicked while the panics were caught.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::EventHub;
+    ///
+    /// let manager = EventHub::default();
+    /// manager
+    ///     .add_responder("Plugins", |_: ()| String::from("Markdown"))
+    ///     .unwrap();
+    /// manager
+    ///     .add_responder("Plugins", |_: ()| String::from("Spelling"))
+    ///     .unwrap();
+    ///
+    /// let plugins = manager.request::<String>("Plugins", ()).unwrap();
+    /// assert_eq!(plugins, ["Markdown", "Spelling"]);
+    /// ```
+    pub fn request<R: 'static>(&self, event_kind: &(impl ToOwned<Owned = K> + ?Sized), event_arg: T) -> Result<Vec<R>> {
+        let mut responses = vec![];
+
+        self.collect_responses(&event_kind.to_owned(), event_arg, |response| {
+            responses.push(response);
+
+            ControlFlow::Continue(())
+        })?;
+
+        Ok(responses)
+    }
+
+    /// Sends a request to the responders of an event kind, and returns the first response.
+    ///
+    /// The responders coming after the first one returning values of type `R` are not called.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of request being sent.
+    /// - `event_arg`: The request argument that will be passed to the responder.
+    ///
+    /// # Returns
+    /// - `Ok(Some(R))` with the first response.
+    /// - `Ok(None)` if no responder returns values of type `R`.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed, or if the responder panicked.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::EventHub;
+    ///
+    /// let manager = EventHub::default();
+    /// manager
+    ///     .add_responder("Config Path", |_: ()| "/etc/app.toml")
+    ///     .unwrap();
+    ///
+    /// let path = manager.request_first::<&str>("Config Path", ()).unwrap();
+    /// assert_eq!(path, Some("/etc/app.toml"));
+    /// ```
+    pub fn request_first<R: 'static>(
+        &self,
+        event_kind: &(impl ToOwned<Owned = K> + ?Sized),
+        event_arg: T,
+    ) -> Result<Option<R>> {
+        let mut first = None;
+
+        self.collect_responses(&event_kind.to_owned(), event_arg, |response| {
+            first = Some(response);
+
+            ControlFlow::Break(())
+        })?;
+
+        Ok(first)
+    }
+
+    /// Sends a request to the responders of an event kind, and folds their responses.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of request being sent.
+    /// - `event_arg`: The request argument that will be passed to each responder.
+    /// - `init`: The initial value of the accumulator.
+    /// - `fold`: A function combining the accumulator with each response, in invocation order of the responders.
+    ///
+    /// # Returns
+    /// - `Ok(A)` which is the final value of the accumulator.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed, or if some responders panicked.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::EventHub;
+    ///
+    /// let manager = EventHub::default();
+    /// manager
+    ///     .add_responder("Pending Jobs", |_: ()| 3usize)
+    ///     .unwrap();
+    /// manager
+    ///     .add_responder("Pending Jobs", |_: ()| 4usize)
+    ///     .unwrap();
+    ///
+    /// let total = manager
+    ///     .request_fold("Pending Jobs", (), 0, |total, jobs: usize| total + jobs)
+    ///     .unwrap();
+    /// assert_eq!(total, 7);
+    /// ```
+    pub fn request_fold<R, A, F>(
+        &self,
+        event_kind: &(impl ToOwned<Owned = K> + ?Sized),
+        event_arg: T,
+        init: A,
+        mut fold: F,
+    ) -> Result<A>
+    where
+        R: 'static,
+        F: FnMut(A, R) -> A, {
+        let mut accumulator = Some(init);
+
+        self.collect_responses(&event_kind.to_owned(), event_arg, |response| {
+            accumulator = accumulator.take().map(|acc| fold(acc, response));
+
+            ControlFlow::Continue(())
+        })?;
+
+        Ok(accumulator.expect("The accumulator is always put back after each response"))
+    }
+
+    /// Returns the policy used for handling the listener failures.
+    ///
+    /// # Returns
+    /// - `Ok(ErrorPolicy)` which is the current policy of the hub.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn error_policy(&self) -> Result<ErrorPolicy> {
+        Ok(self.settings()?.error_policy)
+    }
+
+    /// Sets the policy used for handling the listener failures.
+    ///
+    /// The policy is shared by all the clones of the hub and by the emitters created from it.
+    ///
+    /// # Arguments
+    /// - `policy`: The new policy.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the policy was successfully changed.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::{ErrorPolicy, EventHub};
+    ///
+    /// let manager = EventHub::<()>::default();
+    /// manager.set_error_policy(ErrorPolicy::IgnoreErrors).unwrap();
+    /// ```
+    pub fn set_error_policy(&self, policy: ErrorPolicy) -> Result<()> {
+        let mut settings = self
+            .settings
+            .write()
+            .map_err(|_| EmitixError::lock_poisoned("event hub settings"))?;
+
+        settings.error_policy = policy;
+        Ok(())
+    }
+
+    /// Checks if the events bubble up to the listeners of their parent kinds.
+    ///
+    /// # Returns
+    /// - `Ok(bool)` indicating whether the bubbling is enabled.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn bubbling(&self) -> Result<bool> {
+        Ok(self.settings()?.bubbling)
+    }
+
+    /// Enables or disables the bubbling of the events up to the listeners of their parent kinds.
+    ///
+    /// The event kinds are treated as a hierarchy whose levels are separated by dots.
+    /// With bubbling, an event emitted for `orders.item.added` is delivered to the listeners of `orders.item.added`,
+    /// then to the listeners of `orders.item`, then to the listeners of `orders`,
+    /// unless a listener stops its propagation with [`EventContext::stop_propagation`].
+    /// Each level includes the listeners registered for the patterns matching it, called by decreasing priority,
+    /// then in registration order, and a listener reached at several levels is called only once, at the first one.
+    ///
+    /// The setting is shared by all the clones of the hub and by the emitters created from it.
+    ///
+    /// # Arguments
+    /// - `bubbling`: Whether the events bubble up to the parent kinds.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the setting was successfully changed.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::{
+    ///     event_hub::{EventContext, EventHub},
+    ///     EventManager,
+    /// };
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let manager = EventHub::default();
+    /// let calls = Arc::new(Mutex::new(Vec::new()));
+    ///
+    /// manager.set_bubbling(true).unwrap();
+    ///
+    /// let item_calls = Arc::clone(&calls);
+    /// manager
+    ///     .add_contextual_listener(
+    ///         "orders.item",
+    ///         move |item: String, context: &EventContext| {
+    ///             item_calls.lock().unwrap().push("orders.item");
+    ///             if item.is_empty() {
+    ///                 context.stop_propagation();
+    ///             }
+    ///         },
+    ///     )
+    ///     .unwrap();
+    ///
+    /// for event_kind in ["orders", "orders.*", "orders.item.added"] {
+    ///     let calls = Arc::clone(&calls);
+    ///     manager
+    ///         .add_listener(event_kind, move |_: String| {
+    ///             calls.lock().unwrap().push(event_kind)
+    ///         })
+    ///         .unwrap();
+    /// }
+    ///
+    /// manager
+    ///     .emit("orders.item.added", String::from("Book"))
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     calls.lock().unwrap().drain(..).collect::<Vec<_>>(),
+    ///     ["orders.item.added", "orders.item", "orders.*", "orders"]
+    /// );
+    ///
+    /// manager.emit("orders.item.added", String::new()).unwrap();
+    /// assert_eq!(*calls.lock().unwrap(), ["orders.item.added", "orders.item"]);
+    /// ```
+    pub fn set_bubbling(&self, bubbling: bool) -> Result<()> {
+        let mut settings = self
+            .settings
+            .write()
+            .map_err(|_| EmitixError::lock_poisoned("event hub settings"))?;
+
+        settings.bubbling = bubbling;
+        Ok(())
+    }
+
+    /// Checks if the emissions are stamped with metadata.
+    ///
+    /// # Returns
+    /// - `Ok(bool)` indicating whether the envelope mode is enabled.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn envelope_mode(&self) -> Result<bool> {
+        Ok(self.settings()?.envelopes)
+    }
+
+    /// Enables or disables the envelope mode, in which each emission is stamped with metadata.
+    ///
+    /// The metadata are a unique identifier, a sequence number assigned atomically by the hub,
+    /// the emission time, the identifier of the emitter, and the identifiers of the emissions causing it.
+    /// An event emitted by a listener of a stamped emission, on the same thread, is caused by this emission
+    /// and shares its correlation identifier, even if it's emitted to another hub.
+    ///
+    /// The metadata are given to the listeners added with [`EventHub::add_envelope_listener`],
+    /// and to the contextual listeners by [`EventContext::metadata`].
+    ///
+    /// The mode is shared by all the clones of the hub and by the emitters created from it.
+    ///
+    /// # Arguments
+    /// - `enabled`: `true` for stamping the emissions, `false` otherwise.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the mode was successfully changed.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::{event_hub::EventHub, Envelope, EventManager};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let manager = EventHub::default();
+    /// let envelopes = Arc::new(Mutex::new(Vec::new()));
+    ///
+    /// manager.set_envelope_mode(true).unwrap();
+    ///
+    /// let hub = manager.clone();
+    /// manager
+    ///     .add_listener("order.placed", move |order: u32| {
+    ///         hub.emit("invoice.created", order).unwrap()
+    ///     })
+    ///     .unwrap();
+    ///
+    /// let received = Arc::clone(&envelopes);
+    /// manager
+    ///     .add_envelope_listener("*.*", move |envelope: Envelope<u32>| {
+    ///         received.lock().unwrap().push(envelope)
+    ///     })
+    ///     .unwrap();
+    ///
+    /// manager.emit("order.placed", 42).unwrap();
+    ///
+    /// let envelopes = envelopes.lock().unwrap();
+    /// let (invoice, order) = (&envelopes[0], &envelopes[1]);
+    ///
+    /// assert_eq!(invoice.kind, "invoice.created");
+    /// assert_eq!(invoice.seq, order.seq.map(|seq| seq + 1));
+    /// assert_eq!(invoice.correlation_id, order.correlation_id);
+    /// assert_eq!(invoice.causation_id, Some(order.id));
+    /// ```
+    pub fn set_envelope_mode(&self, enabled: bool) -> Result<()> {
+        let mut settings = self
+            .settings
+            .write()
+            .map_err(|_| EmitixError::lock_poisoned("event hub settings"))?;
+
+        settings.envelopes = enabled;
+        Ok(())
+    }
+
+    /// Returns the policy used for handling the panicking listeners.
+    ///
+    /// # Returns
+    /// - `Ok(PanicPolicy)` which is the current panic policy of the hub.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn panic_policy(&self) -> Result<PanicPolicy> {
+        Ok(self.settings()?.panic_policy)
+    }
+
+    /// Sets the policy used for handling the panicking listeners.
+    ///
+    /// The policy is shared by all the clones of the hub and by the emitters created from it.
+    ///
+    /// # Arguments
+    /// - `policy`: The new panic policy.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the policy was successfully changed.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::{EventHub, PanicPolicy};
+    ///
+    /// let manager = EventHub::<()>::default();
+    /// manager.set_panic_policy(PanicPolicy::Catch).unwrap();
+    /// ```
+    pub fn set_panic_policy(&self, policy: PanicPolicy) -> Result<()> {
+        let mut settings = self
+            .settings
+            .write()
+            .map_err(|_| EmitixError::lock_poisoned("event hub settings"))?;
+
+        settings.panic_policy = policy;
+        Ok(())
+    }
+
+    /// Lists the listeners and the responders quarantined after a panic.
+    ///
+    /// # Returns
+    /// - `Ok(Vec<Uuid>)` containing the identifiers of the quarantined listeners and responders.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn quarantined_listeners(&self) -> Result<Vec<Uuid>> {
+        let registry = self.read_registry();
+
+        Ok(registry.quarantined_listeners())
+    }
+
+    /// Releases a quarantined listener or responder, so it's called again by the next emissions or requests.
+    ///
+    /// # Arguments
+    /// - `listener_id`: The identifier of the quarantined listener or responder.
+    ///
+    /// # Returns
+    /// - `Ok(bool)` indicating whether the listener was quarantined.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::{EventHub, PanicPolicy};
+    /// use emitix::EventManager;
+    ///
+    /// let manager = EventHub::default();
+    /// manager
+    ///     .set_panic_policy(PanicPolicy::CatchAndQuarantine)
+    ///     .unwrap();
+    ///
+    /// let listener_id = manager
+    ///     .add_listener("Events You Like", |_: ()| panic!("Not today"))
+    ///     .unwrap();
+    ///
+    /// assert!(manager.emit("Events You Like", ()).is_err());
+    /// assert!(manager.emit("Events You Like", ()).is_ok());
+    /// assert!(manager.release_listener(listener_id).unwrap());
+    /// ```
+    pub fn release_listener(&self, listener_id: Uuid) -> Result<bool> {
+        let registry = self.read_registry();
+
+        Ok(registry.is_quarantined(listener_id) && registry.set_quarantined(listener_id, false))
+    }
+
+    /// Returns a report about the recoveries of the registry from a poisoned lock.
+    ///
+    /// # Returns
+    /// - `HubHealth` which tells whether the registry was recovered, and how many entries were repaired.
+    pub fn health(&self) -> HubHealth {
+        self.read_registry().health()
+    }
+
+    fn read_registry(&self) -> RwLockReadGuard<'_, ListenerRegistry<T, K>> {
+        ListenerRegistry::read_recovered(&self.registry)
+    }
+
+    fn write_registry(&self) -> RwLockWriteGuard<'_, ListenerRegistry<T, K>> {
+        ListenerRegistry::write_recovered(&self.registry)
+    }
+
+    fn read_sticky(&self) -> Result<RwLockReadGuard<'_, StickyValues<T, K>>> {
+        self.sticky
+            .read()
+            .map_err(|_| EmitixError::lock_poisoned("event hub sticky values"))
+    }
+
+    fn write_sticky(&self) -> Result<RwLockWriteGuard<'_, StickyValues<T, K>>> {
+        self.sticky
+            .write()
+            .map_err(|_| EmitixError::lock_poisoned("event hub sticky values"))
     }
 
-    /// Emits an event to all registered listeners.
-    ///
-    /// # Arguments
-    /// - `event_kind`: A string that identifies the type of event being emitted.
-    /// - `event_arg`: The event argument that will be passed to each listener.
+    /// Emits an event for one event kind, from an emitter or not.
+    fn emit_from(&self, source_emitter_id: Option<Uuid>, event_kind: &K, event_arg: T) -> Result<()> {
+        let settings = self.settings()?;
+        let event_kinds = slice::from_ref(event_kind);
+        let context = self.context(&settings, Some(event_kinds), source_emitter_id);
+
+        self.dispatch(
+            self.emission(Some(event_kinds), &event_arg, &context, settings.bubbling)?,
+            event_arg,
+            &settings,
+            &context,
+        )
+    }
+
+    /// Creates the context of an emission, which is stamped with its metadata in envelope mode.
     ///
-    /// # Returns
-    /// - `Ok(())` if the event was successfully emitted.
-    /// - `Err(anyhow::Error)` if access to the `Mutex` failed.
+    /// An emission by a listener of a stamped emission on the same thread is correlated with it.
+    fn context(
+        &self,
+        settings: &HubSettings,
+        event_kinds: Option<&[K]>,
+        source_emitter_id: Option<Uuid>,
+    ) -> EventContext {
+        let event_kind = match event_kinds {
+            Some([event_kind]) => Some(event_kind.clone()),
+            _ => None,
+        };
+
+        if !settings.envelopes {
+            return EventContext::for_kind(event_kind);
+        }
+
+        let id = Uuid::new_v4();
+        let cause = Cause::current();
+        let metadata = EventMetadata {
+            id,
+            seq: Some(self.sequence.fetch_add(1, Ordering::SeqCst)),
+            timestamp: SystemTime::now(),
+            source_emitter_id,
+            correlation_id: cause.map_or(id, |cause| cause.correlation_id),
+            causation_id: cause.map(|cause| cause.event_id),
+        };
+
+        EventContext::stamped(metadata, event_kind)
+    }
+
+    fn journal(&self) -> Result<Option<EventJournal<T, K>>> {
+        let journal = self
+            .journal
+            .read()
+            .map_err(|_| EmitixError::lock_poisoned("event hub journal"))?;
+
+        Ok(journal.clone())
+    }
+
+    fn read_replay(&self) -> Result<RwLockReadGuard<'_, ReplayBuffers<T, K>>> {
+        self.replay
+            .read()
+            .map_err(|_| EmitixError::lock_poisoned("event hub replay windows"))
+    }
+
+    fn write_replay(&self) -> Result<RwLockWriteGuard<'_, ReplayBuffers<T, K>>> {
+        self.replay
+            .write()
+            .map_err(|_| EmitixError::lock_poisoned("event hub replay windows"))
+    }
+
+    /// Takes a snapshot of the listeners reached by an emission, and records the event in the journal
+    /// and in the replay windows of its event kinds.
     ///
-    /// # Example
-    /// ```rust
-    /// use emitix::event_hub::EventHub;
+    /// The event is recorded in the replay windows with the metadata of its context while the listeners are taken,
+    /// so a listener added with history meanwhile receives the event once.
+    fn emission(
+        &self,
+        event_kinds: Option<&[K]>,
+        event_arg: &T,
+        context: &EventContext,
+        bubbling: bool,
+    ) -> Result<Vec<(Uuid, K, Listener<T>)>> {
+        let Some(event_kinds) = event_kinds else {
+            return self.broadcast_emission(event_arg, context);
+        };
+
+        self.journal_emission(event_kinds, event_arg)?;
+        self.recorded_snapshot(event_kinds, event_arg, context, bubbling)
+    }
+
+    /// Records an event in the journal once per event kind, if a journal is attached.
+    fn journal_emission(&self, event_kinds: &[K], event_arg: &T) -> Result<()> {
+        if let Some(journal) = self.journal()? {
+            for event_kind in event_kinds {
+                journal.record(event_kind.clone(), event_arg.clone())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Takes a snapshot of the listeners reached by some event kinds,
+    /// while the event is recorded in the replay windows of the event kinds.
+    fn recorded_snapshot(
+        &self,
+        event_kinds: &[K],
+        event_arg: &T,
+        context: &EventContext,
+        bubbling: bool,
+    ) -> Result<Vec<(Uuid, K, Listener<T>)>> {
+        if self.read_replay()?.is_recording(event_kinds) {
+            let mut replay = self.write_replay()?;
+
+            replay.record(event_kinds, event_arg, context.metadata().copied());
+            return self.snapshot(Some(event_kinds), bubbling);
+        }
+
+        self.snapshot(Some(event_kinds), bubbling)
+    }
+
+    /// Takes a snapshot of all the listeners for an emission to all the event kinds,
+    /// and records the event in the journal and in the replay windows under each event kind it reaches,
+    /// which are the event kinds the listeners are registered for, the patterns being left out.
+    fn broadcast_emission(&self, event_arg: &T, context: &EventContext) -> Result<Vec<(Uuid, K, Listener<T>)>> {
+        let journal = self.journal()?;
+
+        // Without journal nor window, the event isn't recorded, so the replay windows aren't locked.
+        if journal.is_none() && self.read_replay()?.is_empty() {
+            return self.snapshot(None, false);
+        }
+
+        let mut replay = self.write_replay()?;
+        let listeners = self.snapshot(None, false)?;
+        let mut reached_kinds = Vec::<K>::new();
+
+        for (_, event_kind, _) in &listeners {
+            if !event_kind.is_pattern() && !reached_kinds.contains(event_kind) {
+                reached_kinds.push(event_kind.clone());
+            }
+        }
+
+        if let Some(journal) = journal {
+            for event_kind in &reached_kinds {
+                journal.record(event_kind.clone(), event_arg.clone())?;
+            }
+        }
+
+        replay.record(&reached_kinds, event_arg, context.metadata().copied());
+
+        Ok(listeners)
+    }
+
+    /// Registers a listener whose past events are replayed, and returns it with its identifier
+    /// and the guard holding back the emissions of the other threads until it's dropped.
+    fn register_replayed_listener<F: FnMut(T) + Send + Sync + 'static>(
+        &self,
+        event_kind: &K,
+        listener: F,
+    ) -> (Uuid, Listener<T>, GateGuard) {
+        self.write_registry().register_gated_listener(event_kind, listener)
+    }
+
+    /// Calls a listener with past events, in order.
     ///
-    /// let manager = EventHub::default();
-    /// manager
-    ///     .emit("Events You Like", String::from("Test Event"))
-    ///     .unwrap();
-    /// ```
-    pub fn emit(&self, event_kind: &str, event_arg: T) -> Result<()> {
-        let listeners = self
-            .registry
+    /// The events keep the metadata of their emission, if they were emitted in envelope mode.
+    fn replay_to_listener(
+        &self,
+        listener_id: Uuid,
+        listener: &Listener<T>,
+        events: Vec<(K, Option<EventMetadata>, T)>,
+        settings: &HubSettings,
+    ) -> Result<()> {
+        for (event_kind, metadata, event_arg) in events {
+            let context = match metadata {
+                Some(metadata) => EventContext::stamped(metadata, Some(event_kind.clone())),
+                None => EventContext::for_kind(Some(event_kind.clone())),
+            };
+            let listeners = vec![(listener_id, event_kind, listener.clone())];
+
+            self.dispatch(listeners, event_arg, settings, &context)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks if a listener registered for an event kind or a pattern is reached by the emission of an event kind.
+    fn reaches(listener_kind: &K, event_kind: &K, bubbling: bool) -> bool {
+        let mut event_kind = Some(event_kind.clone());
+
+        while let Some(kind) = event_kind {
+            if *listener_kind == kind || (listener_kind.is_pattern() && listener_kind.matches(&kind)) {
+                return true;
+            }
+
+            event_kind = if bubbling { kind.parent() } else { None };
+        }
+
+        false
+    }
+
+    fn settings(&self) -> Result<HubSettings> {
+        let settings = self
+            .settings
             .read()
-            .map_err(|err| Error::msg(format!("Mutex lock failed in event hub: {err}")))?
-            .listeners()
-            .get(event_kind)
-            .map(|list| list.values().cloned().collect::<Vec<_>>())
-            .unwrap_or_default();
-
-        let mut errors = vec![];
-        for listener in listeners {
-            if let Err(err) = listener.call(event_arg.clone()) {
-                errors.push(err);
+            .map_err(|_| EmitixError::lock_poisoned("event hub settings"))?;
+
+        Ok(settings.clone())
+    }
+
+    /// Takes a snapshot of the listeners reached by some event kinds, or of all the listeners with `None`.
+    ///
+    /// The listeners are sorted by decreasing priority, then in registration order, even across event kinds.
+    /// With bubbling, they are followed by the listeners of the parent kinds, level by level.
+    /// The registry is not locked anymore when the listeners are called,
+    /// so listeners can add or remove listeners.
+    fn snapshot(&self, event_kinds: Option<&[K]>, bubbling: bool) -> Result<Vec<(Uuid, K, Listener<T>)>> {
+        let registry = self.read_registry();
+        let listeners = match event_kinds {
+            Some(event_kinds) if bubbling => registry.bubbling_listeners(event_kinds),
+            _ => registry.matching_listeners(event_kinds),
+        };
+
+        Ok(listeners
+            .into_iter()
+            .map(|(event_kind, entry)| (entry.id, event_kind.clone(), entry.listener.clone()))
+            .collect())
+    }
+
+    fn dispatch(
+        &self,
+        listeners: Vec<(Uuid, K, Listener<T>)>,
+        event_arg: T,
+        settings: &HubSettings,
+        context: &EventContext,
+    ) -> Result<()> {
+        let catch_panics = settings.panic_policy.catches_panics();
+
+        // The listeners run as listeners of the emission, so their emissions are correlated with it.
+        let (failures, panicked) = Cause::scope(context.metadata().map(Cause::of), || {
+            let mut failures = Failures::new(&settings.error_policy);
+            let mut panicked = vec![];
+
+            for (id, kind, listener) in listeners {
+                if context.is_propagation_stopped() {
+                    break;
+                }
+
+                if listener.is_quarantined() {
+                    continue;
+                }
+
+                if let Err(source) = listener.call(event_arg.clone(), context, catch_panics) {
+                    if source.is::<ListenerPanic>() {
+                        panicked.push(id);
+                    }
+
+                    let failure = ListenerFailure {
+                        id,
+                        kind: kind.name(),
+                        source,
+                    };
+
+                    if failures.push(failure).is_break() {
+                        break;
+                    }
+                }
+            }
+
+            (failures, panicked)
+        });
+
+        self.isolate(panicked, settings.panic_policy);
+        failures.finish()
+    }
+
+    /// Calls the responders reached by a request returning values of type `R`, until the handler breaks.
+    fn collect_responses<R, F>(&self, event_kind: &K, event_arg: T, mut handler: F) -> Result<()>
+    where
+        R: 'static,
+        F: FnMut(R) -> ControlFlow<()>, {
+        let settings = self.settings()?;
+        let responders = {
+            let registry = self.read_registry();
+
+            registry
+                .matching_responders(event_kind, settings.bubbling)
+                .into_iter()
+                .map(|(kind, entry)| (entry.id, kind.clone(), entry.listener.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        let catch_panics = settings.panic_policy.catches_panics();
+        let mut failures = Failures::new(&settings.error_policy);
+        let mut panicked = vec![];
+
+        for (id, kind, responder) in responders {
+            if responder.is_quarantined() {
+                continue;
+            }
+
+            let flow = match responder.respond(event_arg.clone(), catch_panics) {
+                None => continue,
+                Some(Ok(response)) => handler(response),
+                Some(Err(source)) => {
+                    if source.is::<ListenerPanic>() {
+                        panicked.push(id);
+                    }
+
+                    failures.push(ListenerFailure {
+                        id,
+                        kind: kind.name(),
+                        source,
+                    })
+                }
+            };
+
+            if flow.is_break() {
+                break;
             }
         }
 
-        if !errors.is_empty() {
-            return Err(Error::msg(format!(
-                "Failed to emit event '{event_kind}':\n{}",
-                errors.into_iter().map(|err| format!("\n  - {err}")).collect::<String>(),
-            )));
+        self.isolate(panicked, settings.panic_policy);
+        failures.finish()
+    }
+
+    /// Removes or quarantines the listeners and the responders which panicked, according to the panic policy.
+    fn isolate(&self, panicked: Vec<Uuid>, policy: PanicPolicy) {
+        match policy {
+            _ if panicked.is_empty() => {}
+            PanicPolicy::CatchAndRemove => {
+                let mut registry = self.write_registry();
+
+                for id in panicked {
+                    registry.remove_listener(id);
+                }
+            }
+            PanicPolicy::CatchAndQuarantine => {
+                let registry = self.read_registry();
+
+                for id in panicked {
+                    registry.set_quarantined(id, true);
+                }
+            }
+            PanicPolicy::Propagate | PanicPolicy::Catch => {}
         }
+    }
 
-        Ok(())
+    /// Creates a subscription removing a listener from the registry, without keeping the registry alive.
+    fn subscription(&self, listener_id: Uuid) -> Subscription {
+        let registry = Arc::downgrade(&self.registry);
+
+        Subscription::new(listener_id, move |listener_id| {
+            if let Some(registry) = registry.upgrade() {
+                ListenerRegistry::write_recovered(&registry).remove_listener(listener_id);
+            }
+        })
+    }
+}
+
+impl<T: Event> EventHub<T> {
+    /// Emits an event for the event kind of its variant.
+    ///
+    /// # Arguments
+    /// - `event`: The event passed to each listener of its event kind.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the event was successfully emitted.
+    /// - `Err(EmitixError::LockPoisoned)` if access to the `Mutex` failed.
+    /// - `Err(EmitixError::ListenerFailures)` if some listeners returned an error.
+    pub fn emit_event(&self, event: T) -> Result<()> {
+        self.emit(event.kind(), event)
     }
 }
 
-impl<T: Clone + Send + Sync + 'static> Default for EventHub<T> {
+impl<T: Clone + Send + Sync + 'static, K: EventKey> Default for EventHub<T, K> {
     fn default() -> Self {
         Self {
             registry: Arc::new(RwLock::new(ListenerRegistry::new())),
+            settings: Arc::new(RwLock::new(HubSettings::default())),
+            sticky:   Arc::new(RwLock::new(HashMap::new())),
+            replay:   Arc::new(RwLock::new(ReplayBuffers::new())),
+            journal:  Arc::new(RwLock::new(None)),
+            sequence: Arc::new(AtomicU64::new(0)),
         }
     }
 }
 
-impl<T: Clone + Send + Sync + 'static> EventManager<T> for EventHub<T> {
+impl<T: Clone + Send + Sync + 'static, K: EventKey> EventManager<T, K> for EventHub<T, K> {
     /// Lists all event kinds that have registered listeners.
     ///
+    /// The event kinds with responders only are not listed, as an emission doesn't reach the responders.
+    ///
     /// # Returns
-    /// - `Ok(Vec<String>)` containing the names of all event kinds.
-    /// - `Err(anyhow::Error)` if access to the `Mutex` failed.
+    /// - `Ok(Vec<K>)` containing the keys of all event kinds and patterns.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
     ///
     /// # Example
     /// ```rust
     /// use emitix::{event_hub::EventHub, EventManager};
     ///
-    /// let manager = EventHub::default();
+    /// let manager = EventHub::<()>::default();
     /// let list = manager.list_event_kinds().unwrap();
     /// ```
-    fn list_event_kinds(&self) -> Result<Vec<String>> {
-        let registry = self
-            .registry
-            .read()
-            .map_err(|err| Error::msg(format!("Mutex lock failed in event hub: {err}")))?;
+    fn list_event_kinds(&self) -> Result<Vec<K>> {
+        let registry = self.read_registry();
 
-        Ok(registry.listeners().keys().cloned().collect::<Vec<_>>())
+        Ok(registry.listeners().keys().cloned().collect())
     }
 
     /// Checks if there are any listeners for a specific event kind.
+    ///
+    /// The listeners registered for the patterns matching the event kind are taken into account,
+    /// but not the responders, see [`EventHub::has_responders`].
+    ///
     /// # Arguments
-    /// - `event_kind`: A string that identifies the type of event to check for listeners.
+    /// - `event_kind`: A key that identifies the type of event to check for listeners.
     ///
     /// # Returns
     /// - `Ok(bool)` indicating whether there are listeners for the specified event kind.
-    /// - `Err(anyhow::Error)` if access to the `Mutex` failed.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
     ///
     /// # Example
     /// ```rust
     /// use emitix::{event_hub::EventHub, EventManager};
     ///
-    /// let manager = EventHub::default();
-    /// let has_listeners = manager.has_listeners("Events You Like").unwrap();
-    fn has_listeners(&self, event_kind: &str) -> Result<bool> {
-        let registry = self
-            .registry
-            .read()
-            .map_err(|err| Error::msg(format!("Mutex lock failed in event hub: {err}")))?;
+    /// let manager = EventHub::<()>::default();
+    /// manager.add_listener("user.*", |_| {}).unwrap();
+    ///
+    /// assert!(manager.has_listeners("user.created").unwrap());
+    /// assert!(!manager.has_listeners("order.created").unwrap());
+    /// ```
+    fn has_listeners<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q) -> Result<bool> {
+        let registry = self.read_registry();
 
-        Ok(registry.listeners().contains_key(event_kind))
+        Ok(registry.listeners_count(&event_kind.to_owned()) > 0)
     }
 
     /// Returns the number of listeners for a specific event kind.
     ///
+    /// It's the number of listeners reached by an emission of the event kind,
+    /// including the listeners registered for the patterns matching it.
+    /// The responders are counted apart, see [`EventHub::responders_count`].
+    ///
     /// # Arguments
-    /// - `event_kind`: A string that identifies the type of event whose listeners count is requested.
+    /// - `event_kind`: A key that identifies the type of event whose listeners count is requested.
     ///
     /// # Returns
     /// - `Ok(usize)` representing the number of listeners for the specified event kind.
-    /// - `Err(anyhow::Error)` if access to the `Mutex` failed.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
     ///
     /// # Example
     /// ```rust
     /// use emitix::{event_hub::EventHub, EventManager};
     ///
-    /// let manager = EventHub::default();
+    /// let manager = EventHub::<()>::default();
     /// let count = manager.listeners_count("Events You Like").unwrap();
-    fn listeners_count(&self, event_kind: &str) -> Result<usize> {
-        let registry = self
-            .registry
-            .read()
-            .map_err(|err| Error::msg(format!("Mutex lock failed in event hub: {err}")))?;
+    /// ```
+    fn listeners_count<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q) -> Result<usize> {
+        let registry = self.read_registry();
 
-        Ok(registry.listeners().get(event_kind).map_or(0, |l| l.len()))
+        Ok(registry.listeners_count(&event_kind.to_owned()))
     }
 
     /// Clears all listeners for a specific event kind.
     ///
     /// # Arguments
-    /// - `event_kind`: A string that identifies the type of event whose listeners should be cleared.
+    /// - `event_kind`: A key that identifies the type of event whose listeners should be cleared.
     ///
     /// # Returns
     /// - `Ok(())` if the listeners were successfully cleared.
-    /// - `Err(anyhow::Error)` if access to the `Mutex` failed.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
     ///
     /// # Example
     /// ```rust
     /// use emitix::{event_hub::EventHub, EventManager};
     ///
-    /// let manager = EventHub::default();
+    /// let manager = EventHub::<()>::default();
     /// manager.clear_listeners().unwrap()
     /// ```
     fn clear_listeners(&self) -> Result<()> {
-        let mut registry = self
-            .registry
-            .write()
-            .map_err(|err| Error::msg(format!("Mutex lock failed in event hub: {err}")))?;
+        let mut registry = self.write_registry();
 
         registry.clear();
         Ok(())
@@ -199,13 +2276,16 @@ impl<T: Clone + Send + Sync + 'static> EventManager<T> for EventHub<T> {
 
     /// Adds an event listener.
     ///
+    /// The event kind can be a pattern, whose `*` segment matches one segment of the emitted event kinds
+    /// and whose `**` segment matches any number of segments, the segments being separated by dots.
+    ///
     /// # Arguments
-    /// - `event_kind`: A string that identifies the type of event this listener is for.
+    /// - `event_kind`: A key that identifies the type of event this listener is for.
     /// - `listener`: A function or closure that will be called when the event is emitted.
     ///
     /// # Returns
     /// - `Ok(())` if the listener was successfully added.
-    /// - `Err(anyhow::Error)` if access to the `Mutex` failed.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
     ///
     /// # Example
     /// ```rust
@@ -217,25 +2297,178 @@ impl<T: Clone + Send + Sync + 'static> EventManager<T> for EventHub<T> {
     ///         println!("Event received: {}", event);
     ///     })
     ///     .unwrap();
+    ///
+    /// manager
+    ///     .add_listener("user.**", |event: String| {
+    ///         println!("Audit: {}", event);
+    ///     })
+    ///     .unwrap();
+    ///
+    /// manager
+    ///     .emit("user.profile.updated", String::from("Test Event"))
+    ///     .unwrap();
     /// ```
-    fn add_listener<F: FnMut(T) + Send + Sync + 'static>(&self, event_kind: &str, listener: F) -> Result<Uuid> {
-        let mut registry = self
-            .registry
-            .write()
-            .map_err(|err| Error::msg(format!("Mutex lock failed in event hub: {err}")))?;
+    fn add_listener<Q: ?Sized + ToOwned<Owned = K>, F: FnMut(T) + Send + Sync + 'static>(
+        &self,
+        event_kind: &Q,
+        listener: F,
+    ) -> Result<Uuid> {
+        let mut registry = self.write_registry();
+
+        Ok(registry.register_listener(&event_kind.to_owned(), listener))
+    }
+
+    /// Adds an event listener with a priority.
+    ///
+    /// Listeners with a higher priority are called first,
+    /// and listeners with the same priority are called in registration order.
+    /// Listeners added without priority have the priority `0`.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event this listener is for.
+    /// - `priority`: The priority of the listener.
+    /// - `listener`: A function or closure that will be called when the event is emitted.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the listener.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::{event_hub::EventHub, EventManager};
+    ///
+    /// let manager = EventHub::default();
+    /// manager
+    ///     .add_listener("Order Placed", |order: u32| {
+    ///         println!("Saving order {order}")
+    ///     })
+    ///     .unwrap();
+    ///
+    /// manager
+    ///     .add_listener_with_priority("Order Placed", 10, |order: u32| {
+    ///         println!("Validating order {order}")
+    ///     })
+    ///     .unwrap();
+    ///
+    /// manager.emit("Order Placed", 42).unwrap();
+    /// ```
+    fn add_listener_with_priority<Q: ?Sized + ToOwned<Owned = K>, F: FnMut(T) + Send + Sync + 'static>(
+        &self,
+        event_kind: &Q,
+        priority: i32,
+        listener: F,
+    ) -> Result<Uuid> {
+        let mut registry = self.write_registry();
+
+        Ok(registry.register_listener_with_priority(&event_kind.to_owned(), priority, listener))
+    }
 
-        Ok(registry.register_listener(event_kind, listener))
+    /// Adds an event listener which can fail.
+    ///
+    /// The errors returned by the listener are reported by `emit` and by the emitters of the hub.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event this listener is for.
+    /// - `listener`: A function or closure that will be called when the event is emitted.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the listener.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::{event_hub::EventHub, EventManager};
+    /// use anyhow::bail;
+    ///
+    /// let manager = EventHub::default();
+    /// manager
+    ///     .add_fallible_listener("Events You Like", |event: String| {
+    ///         if event.is_empty() {
+    ///             bail!("Empty event");
+    ///         }
+    ///
+    ///         Ok(())
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert!(manager.emit("Events You Like", String::new()).is_err());
+    /// ```
+    fn add_fallible_listener<
+        Q: ?Sized + ToOwned<Owned = K>,
+        F: FnMut(T) -> anyhow::Result<()> + Send + Sync + 'static,
+    >(
+        &self,
+        event_kind: &Q,
+        listener: F,
+    ) -> Result<Uuid> {
+        let mut registry = self.write_registry();
+
+        Ok(registry.register_fallible_listener(&event_kind.to_owned(), listener))
+    }
+
+    /// Adds an event listener which is removed after being called a given number of times.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event this listener is for.
+    /// - `max_calls`: The number of calls after which the listener is removed, which can't be zero.
+    /// - `listener`: A function or closure that will be called when the event is emitted.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the listener.
+    /// - `Err(EmitixError::InvalidArgument)` if `max_calls` is zero, no listener is registered.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::{event_hub::EventHub, EventManager};
+    ///
+    /// let manager = EventHub::default();
+    /// manager
+    ///     .add_listener_limited("Events You Like", 2, |event: String| {
+    ///         println!("Event received: {}", event);
+    ///     })
+    ///     .unwrap();
+    ///
+    /// manager
+    ///     .emit("Events You Like", String::from("First"))
+    ///     .unwrap();
+    /// manager
+    ///     .emit("Events You Like", String::from("Second"))
+    ///     .unwrap();
+    /// assert!(!manager.has_listeners("Events You Like").unwrap());
+    /// assert!(
+    ///     manager
+    ///         .add_listener_limited("Events You Like", 0, |_| {})
+    ///         .is_err()
+    /// );
+    /// ```
+    fn add_listener_limited<Q: ?Sized + ToOwned<Owned = K>, F: FnMut(T) + Send + Sync + 'static>(
+        &self,
+        event_kind: &Q,
+        max_calls: usize,
+        listener: F,
+    ) -> Result<Uuid> {
+        let max_calls = NonZeroUsize::new(max_calls)
+            .ok_or_else(|| EmitixError::invalid_argument("max_calls", "must not be zero"))?;
+        let mut registry = self.write_registry();
+
+        Ok(registry.register_limited_listener(
+            Arc::downgrade(&self.registry),
+            &event_kind.to_owned(),
+            max_calls,
+            listener,
+        ))
     }
 
     /// Removes a listener for a specific event kind.
     ///
     /// # Arguments
-    /// - `event_kind`: A string that identifies the type of event this listener is for.
+    /// - `event_kind`: A key that identifies the type of event this listener is for.
     /// - `listener`: The listener function or closure to be removed.
     ///
     /// # Returns
     /// - `Ok(())` if the listener was successfully removed.
-    /// - `Err(anyhow::Error)` if access to the `Mutex` failed.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
     ///
     /// # Example
     /// ```rust
@@ -250,43 +2483,37 @@ impl<T: Clone + Send + Sync + 'static> EventManager<T> for EventHub<T> {
     /// manager.remove_listener(listener_id).unwrap();
     /// ```
     fn remove_listener(&self, listener_id: Uuid) -> Result<bool> {
-        let mut registry = self
-            .registry
-            .write()
-            .map_err(|err| Error::msg(format!("Mutex lock failed in event hub: {err}")))?;
+        let mut registry = self.write_registry();
 
         Ok(registry.remove_listener(listener_id))
     }
 
-    /// Clears all listeners for a specific event kind.
+    /// Removes all listeners and responders registered for a specific event kind or pattern.
     ///
     /// # Arguments
-    /// - `event_kind`: A string that identifies the type of event whose listeners should be cleared.
+    /// - `event_kind`: A key that identifies the type of event whose listeners should be cleared.
     ///
     /// # Returns
-    /// - `Ok(())` if the listeners were successfully cleared.
-    /// - `Err(anyhow::Error)` if access to the `Mutex` failed.
+    /// - `Ok(usize)` representing the number of removed listeners and responders.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
     ///
     /// # Example
     /// ```rust
     /// use emitix::{event_hub::EventHub, EventManager};
     ///
-    /// let manager = EventHub::default();
-    /// manager.remove_listeners_by_kind("Events You Like").unwrap()
+    /// let manager = EventHub::<()>::default();
+    /// manager.remove_listeners_by_kind("Events You Like").unwrap();
     /// ```
-    fn remove_listeners_by_kind(&self, event_kind: &str) -> Result<usize> {
-        let mut registry = self
-            .registry
-            .write()
-            .map_err(|err| Error::msg(format!("Mutex lock failed in event hub: {err}")))?;
+    fn remove_listeners_by_kind<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q) -> Result<usize> {
+        let mut registry = self.write_registry();
 
-        Ok(registry.remove_listeners_by_kind(event_kind))
+        Ok(registry.remove_listeners_by_kind(&event_kind.to_owned()))
     }
 
     /// Creates a new event emitter for a specific event kind.
     ///
     /// # Arguments
-    /// - `event_kind`: A string that identifies the type of event this emitter will handle.
+    /// - `event_kind`: A key that identifies the type of event this emitter will handle.
     ///
     /// # Returns
     /// - `Box<dyn EventEmitter<T>>` which is a boxed trait object that implements the `EventEmitter` trait.
@@ -299,46 +2526,20 @@ impl<T: Clone + Send + Sync + 'static> EventManager<T> for EventHub<T> {
     /// let emitter = manager.new_emitter("Events You Like");
     /// emitter.emit(()).unwrap()
     /// ```
-    fn new_emitter(&self, event_kind: &str) -> Box<dyn EventEmitter<T>> {
-        let event_kind = event_kind.to_string();
-        let registry = Arc::clone(&self.registry);
-        let listener = Listener::new(move |event_arg: T| -> Result<()> {
-            let listeners = registry
-                .read()
-                .map_err(|err| {
-                    let msg = format!("Mutex lock failed in event hub for kind `{event_kind}`: {err}");
-
-                    Error::msg(msg)
-                })?
-                .listeners()
-                .get(&event_kind)
-                .map(|listeners| listeners.values().cloned().collect::<Vec<_>>())
-                .unwrap_or_default();
-
-            let mut errors = vec![];
-            for listener in listeners {
-                if let Err(err) = listener.call(event_arg.clone()) {
-                    errors.push(err);
-                }
-            }
-
-            if !errors.is_empty() {
-                return Err(Error::msg(format!(
-                    "Failed to emit event '{event_kind}':{errors}",
-                    errors = errors.into_iter().map(|err| format!("\n  - {err}")).collect::<String>(),
-                )));
-            }
+    fn new_emitter<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q) -> Box<dyn EventEmitter<T>> {
+        let event_kind = event_kind.to_owned();
+        let emitter_id = Uuid::new_v4();
+        let hub = self.clone();
 
-            Ok(())
-        });
-
-        Box::new(EventHubEmitter::new(listener))
+        Box::new(EventHubEmitter::new(emitter_id, move |event_arg: T| {
+            hub.emit_from(Some(emitter_id), &event_kind, event_arg)
+        }))
     }
 
     /// Creates a new event broadcaster that emits events to multiple listeners.
     ///
     /// # Arguments
-    /// - `event_kinds`: A slice of strings that identifies the types of events this broadcaster will handle.
+    /// - `event_kinds`: A slice of keys that identifies the types of events this broadcaster will handle.
     ///
     /// # Returns
     /// - `Box<dyn EventEmitter<T>>` which is a boxed trait object that implements the `EventEmitter` trait.
@@ -351,57 +2552,27 @@ impl<T: Clone + Send + Sync + 'static> EventManager<T> for EventHub<T> {
     /// let broadcaster = manager.new_broadcast_emitter(&["Events You Like", "Another Event Kind"]);
     /// broadcaster.emit(()).unwrap()
     /// ```
-    fn new_broadcast_emitter(&self, event_kinds: &[&str]) -> Box<dyn EventEmitter<T>> {
+    fn new_broadcast_emitter<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kinds: &[&Q]) -> Box<dyn EventEmitter<T>> {
         let event_kinds = if event_kinds.is_empty() {
             None
         } else {
-            Some(event_kinds.iter().map(|&s| s.to_string()).collect::<Vec<_>>())
+            Some(event_kinds.iter().map(|&kind| kind.to_owned()).collect::<Vec<_>>())
         };
 
-        let registry = Arc::clone(&self.registry);
-        let listener = Listener::new(move |event_arg: T| -> Result<()> {
-            let (listeners, event_kinds) = {
-                let registry = registry.read().map_err(|err| {
-                    let event_kinds = event_kinds.as_ref().map(|l| l.join(", ")).unwrap_or_default();
-                    let msg = format!("Mutex lock failed in event hub for kind `{event_kinds}`: {err}");
-
-                    Error::msg(msg)
-                })?;
-
-                let listeners = registry.listeners();
-                let kinds_to_process = match &event_kinds {
-                    Some(list) => list.clone(),
-                    None => listeners.keys().cloned().collect::<Vec<_>>(),
-                };
-
-                let mut event_listeners = Vec::new();
-                for event_kind in &kinds_to_process {
-                    if let Some(callbacks) = listeners.get(event_kind) {
-                        event_listeners.extend(callbacks.values().cloned());
-                    }
-                }
-
-                (event_listeners, kinds_to_process.join(", "))
-            };
-
-            let mut errors = vec![];
-            for listener in listeners {
-                if let Err(err) = listener.call(event_arg.clone()) {
-                    errors.push(err);
-                }
-            }
-
-            if !errors.is_empty() {
-                return Err(Error::msg(format!(
-                    "Failed to emit event from hub for kinds '{event_kinds}':{errors}",
-                    errors = errors.into_iter().map(|err| format!("\n  - {err}")).collect::<String>(),
-                )));
-            }
+        let emitter_id = Uuid::new_v4();
+        let hub = self.clone();
 
-            Ok(())
-        });
+        Box::new(EventHubEmitter::new(emitter_id, move |event_arg: T| {
+            let settings = hub.settings()?;
+            let context = hub.context(&settings, event_kinds.as_deref(), Some(emitter_id));
 
-        Box::new(EventHubEmitter::new(listener))
+            hub.dispatch(
+                hub.emission(event_kinds.as_deref(), &event_arg, &context, settings.bubbling)?,
+                event_arg,
+                &settings,
+                &context,
+            )
+        }))
     }
 
     /// Returns a null emitter used as default emitter.
@@ -409,6 +2580,6 @@ impl<T: Clone + Send + Sync + 'static> EventManager<T> for EventHub<T> {
     /// # Returns
     /// - `Box<dyn EventEmitter<T>>` which is a boxed trait object that implements the `EventEmitter` trait.
     fn new_null_emitter() -> Box<dyn EventEmitter<T>> {
-        Box::new(EventHubEmitter::new(Listener::new(|_| Ok(()))))
+        Box::new(EventHubEmitter::new(Uuid::new_v4(), |_| Ok(())))
     }
 }
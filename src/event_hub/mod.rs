@@ -1,6 +1,29 @@
+mod context;
+mod correlation;
 mod emitter;
+#[cfg(feature = "file-journal")]
+mod file_journal;
+mod journal;
 mod listener;
 mod manager;
+mod policy;
 mod registry;
+mod replay;
+mod responder;
+mod stream;
+mod typed;
+mod wait;
 
-pub use manager::EventHub;
+pub use self::{
+    context::EventContext,
+    journal::{EventJournal, JournalEntry, JournalSink, MemoryJournal, SequenceRange},
+    manager::EventHub,
+    policy::{ErrorPolicy, PanicPolicy},
+    replay::ReplayWindow,
+    stream::EventStream,
+    typed::TypedEventHub,
+    wait::EventWait,
+};
+
+#[cfg(feature = "file-journal")]
+pub use self::file_journal::{FileJournal, FileJournalOptions, FsyncPolicy, JournalRecords};
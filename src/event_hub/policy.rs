@@ -0,0 +1,156 @@
+use crate::{EmitixError, ListenerFailure, Result};
+use std::{fmt, ops::ControlFlow, sync::Arc};
+
+type FailureHandler = Arc<dyn Fn(ListenerFailure) + Send + Sync>;
+
+/// The way listener failures are handled while an event is dispatched.
+///
+/// It applies equally to `EventHub::emit`, `EventHub::emit_async`
+/// and to the emitters created by the hub.
+///
+/// # Example
+/// ```rust
+/// use emitix::event_hub::{ErrorPolicy, EventHub};
+/// use emitix::EventManager;
+/// use anyhow::bail;
+///
+/// let manager = EventHub::default();
+/// manager
+///     .add_fallible_listener("Events You Like", |_: String| bail!("Not today"))
+///     .unwrap();
+///
+/// manager
+///     .set_error_policy(ErrorPolicy::route(|failure| eprintln!("{failure}")))
+///     .unwrap();
+/// assert!(
+///     manager
+///         .emit("Events You Like", String::from("Test Event"))
+///         .is_ok()
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub enum ErrorPolicy {
+    /// Calls every listener and reports all the failures, it's the default policy.
+    #[default]
+    ContinueAndCollect,
+    /// Stops the dispatch at the first failing listener and reports its failure.
+    StopOnFirstError,
+    /// Calls every listener and discards the failures.
+    IgnoreErrors,
+    /// Calls every listener and sends each failure to a handler instead of reporting it.
+    Route(FailureHandler),
+}
+
+impl ErrorPolicy {
+    /// Creates a policy routing each listener failure to a handler.
+    pub fn route<F: Fn(ListenerFailure) + Send + Sync + 'static>(handler: F) -> Self {
+        Self::Route(Arc::new(handler))
+    }
+}
+
+impl fmt::Debug for ErrorPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ContinueAndCollect => f.write_str("ContinueAndCollect"),
+            Self::StopOnFirstError => f.write_str("StopOnFirstError"),
+            Self::IgnoreErrors => f.write_str("IgnoreErrors"),
+            Self::Route(_) => f.write_str("Route(..)"),
+        }
+    }
+}
+
+/// The way panicking listeners are handled while an event is dispatched.
+///
+/// When panics are caught, a panic becomes a listener failure whose source is
+/// a [`ListenerPanic`](crate::ListenerPanic), reported according to the error policy,
+/// and the remaining listeners are called.
+///
+/// # Example
+/// ```rust
+/// use emitix::event_hub::{EventHub, PanicPolicy};
+/// use emitix::EventManager;
+///
+/// let manager = EventHub::default();
+/// manager
+///     .set_panic_policy(PanicPolicy::CatchAndRemove)
+///     .unwrap();
+/// manager
+///     .add_listener("Events You Like", |_: String| panic!("Not today"))
+///     .unwrap();
+///
+/// let err = manager
+///     .emit("Events You Like", String::from("Test Event"))
+///     .unwrap_err();
+///
+/// assert!(err.failures()[0].is_panic());
+/// assert!(!manager.has_listeners("Events You Like").unwrap());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PanicPolicy {
+    /// Lets the panic unwind through the emission, it's the default policy.
+    #[default]
+    Propagate,
+    /// Catches the panic and reports it as a listener failure.
+    Catch,
+    /// Catches the panic, reports it, and removes the panicking listener.
+    CatchAndRemove,
+    /// Catches the panic, reports it, and quarantines the panicking listener.
+    ///
+    /// Quarantined listeners stay registered but are not called anymore, until they are released.
+    CatchAndQuarantine,
+}
+
+impl PanicPolicy {
+    pub(super) fn catches_panics(self) -> bool {
+        self != Self::Propagate
+    }
+}
+
+/// The dispatch settings shared by the clones of a hub and by its emitters.
+#[derive(Clone, Default)]
+pub(super) struct HubSettings {
+    pub(super) error_policy: ErrorPolicy,
+    pub(super) panic_policy: PanicPolicy,
+    pub(super) bubbling:     bool,
+    pub(super) envelopes:    bool,
+}
+
+/// Collects the listener failures of one dispatch according to an error policy.
+pub(super) struct Failures<'a> {
+    policy:   &'a ErrorPolicy,
+    failures: Vec<ListenerFailure>,
+}
+
+impl<'a> Failures<'a> {
+    pub(super) fn new(policy: &'a ErrorPolicy) -> Self {
+        Self {
+            policy,
+            failures: vec![],
+        }
+    }
+
+    /// Handles a failure, and tells whether the dispatch must go on.
+    pub(super) fn push(&mut self, failure: ListenerFailure) -> ControlFlow<()> {
+        match self.policy {
+            ErrorPolicy::ContinueAndCollect => self.failures.push(failure),
+            ErrorPolicy::StopOnFirstError => {
+                self.failures.push(failure);
+
+                return ControlFlow::Break(());
+            }
+            ErrorPolicy::IgnoreErrors => {}
+            ErrorPolicy::Route(handler) => handler(failure),
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    pub(super) fn finish(self) -> Result<()> {
+        if self.failures.is_empty() {
+            Ok(())
+        } else {
+            Err(EmitixError::ListenerFailures(self.failures))
+        }
+    }
+}
@@ -1,77 +1,289 @@
-use super::listener::Listener;
+use super::{
+    context::EventContext,
+    listener::{GateGuard, Listener},
+    responder::Responder,
+};
+use crate::{
+    registry::{ListenerEntry, ListenerMap, Recoverable, RegistryCore, DEFAULT_PRIORITY},
+    EventKey,
+    HubHealth,
+};
+use anyhow::Result;
 use uuid::Uuid;
-use std::collections::HashMap;
+use std::{
+    future::Future,
+    slice,
+    num::NonZeroUsize,
+    sync::{RwLock, Weak},
+};
 
-pub(super) type ListenerMap<T> = HashMap<String, HashMap<Uuid, Listener<T>>>;
-
-pub(super) struct ListenerRegistry<T: Clone + Send + Sync + 'static> {
-    listeners: ListenerMap<T>,
-    links:     HashMap<Uuid, String>,
+/// The listeners and the responders of a hub.
+///
+/// The responders are stored apart from the listeners, so the emissions don't reach them,
+/// but they are registered, matched and removed the same way.
+pub(super) struct ListenerRegistry<T: Clone + Send + Sync + 'static, K: EventKey> {
+    listeners:  RegistryCore<Listener<T>, K>,
+    responders: RegistryCore<Responder<T>, K>,
+    health:     HubHealth,
 }
 
-impl<T: Clone + Send + Sync + 'static> ListenerRegistry<T> {
+impl<T: Clone + Send + Sync + 'static, K: EventKey> ListenerRegistry<T, K> {
     pub(super) fn new() -> Self {
         Self {
-            listeners: HashMap::new(),
-            links:     HashMap::new(),
+            listeners:  RegistryCore::new(),
+            responders: RegistryCore::new(),
+            health:     HubHealth::default(),
         }
     }
 
     pub(super) fn clear(&mut self) {
         self.listeners.clear();
-        self.links.clear();
+        self.responders.clear();
     }
 
-    pub(super) fn listeners(&self) -> &ListenerMap<T> {
-        &self.listeners
+    pub(super) fn health(&self) -> HubHealth {
+        self.health
     }
 
-    pub(super) fn remove_listener(&mut self, listener_id: Uuid) -> bool {
-        if let Some(event_kind) = self.links.remove(&listener_id) {
-            if let Some(listeners) = self.listeners.get_mut(&event_kind) {
-                listeners.remove(&listener_id);
-                if listeners.is_empty() {
-                    self.listeners.remove(&event_kind);
-                }
+    pub(super) fn listeners(&self) -> &ListenerMap<Listener<T>, K> {
+        self.listeners.listeners()
+    }
+
+    /// Returns the number of listeners reached by the emission of an event kind.
+    pub(super) fn listeners_count(&self, event_kind: &K) -> usize {
+        self.listeners
+            .matching_listeners(Some(slice::from_ref(event_kind)))
+            .len()
+    }
+
+    /// Returns the number of responders reached by a request of an event kind.
+    pub(super) fn responders_count(&self, event_kind: &K) -> usize {
+        self.responders
+            .matching_listeners(Some(slice::from_ref(event_kind)))
+            .len()
+    }
+
+    pub(super) fn quarantined_listeners(&self) -> Vec<Uuid> {
+        let listeners = self
+            .listeners()
+            .values()
+            .flatten()
+            .filter(|entry| entry.listener.is_quarantined())
+            .map(|entry| entry.id);
+        let responders = self
+            .responders
+            .listeners()
+            .values()
+            .flatten()
+            .filter(|entry| entry.listener.is_quarantined())
+            .map(|entry| entry.id);
 
-                return true;
-            }
+        listeners.chain(responders).collect()
+    }
+
+    /// Checks if a listener or a responder is quarantined.
+    pub(super) fn is_quarantined(&self, listener_id: Uuid) -> bool {
+        match self.listeners.listener(listener_id) {
+            Some(listener) => listener.is_quarantined(),
+            None => self
+                .responders
+                .listener(listener_id)
+                .is_some_and(Responder::is_quarantined),
+        }
+    }
+
+    /// Quarantines or releases a listener or a responder, and tells whether it's registered.
+    pub(super) fn set_quarantined(&self, listener_id: Uuid, quarantined: bool) -> bool {
+        if let Some(listener) = self.listeners.listener(listener_id) {
+            listener.set_quarantined(quarantined);
+        } else if let Some(responder) = self.responders.listener(listener_id) {
+            responder.set_quarantined(quarantined);
+        } else {
+            return false;
         }
 
-        false
+        true
     }
 
-    pub(super) fn remove_listeners_by_kind(&mut self, event_kind: &str) -> usize {
-        match self.listeners.remove(event_kind) {
-            None => 0,
-            Some(listeners) => {
-                let sz = listeners.len();
-                for listener_id in listeners.keys() {
-                    self.links.remove(listener_id);
-                }
+    pub(super) fn matching_listeners(&self, event_kinds: Option<&[K]>) -> Vec<(&K, &ListenerEntry<Listener<T>>)> {
+        self.listeners.matching_listeners(event_kinds)
+    }
+
+    pub(super) fn bubbling_listeners(&self, event_kinds: &[K]) -> Vec<(&K, &ListenerEntry<Listener<T>>)> {
+        self.listeners.bubbling_listeners(event_kinds)
+    }
+
+    /// Returns the responders reached by a request, like the listeners reached by an emission.
+    pub(super) fn matching_responders(
+        &self,
+        event_kind: &K,
+        bubbling: bool,
+    ) -> Vec<(&K, &ListenerEntry<Responder<T>>)> {
+        let event_kinds = slice::from_ref(event_kind);
 
-                sz
-            }
+        if bubbling {
+            self.responders.bubbling_listeners(event_kinds)
+        } else {
+            self.responders.matching_listeners(Some(event_kinds))
         }
     }
 
-    pub(super) fn register_listener<F>(&mut self, event_kind: &str, mut listener: F) -> Uuid
+    pub(super) fn remove_listener(&mut self, listener_id: Uuid) -> bool {
+        self.listeners.remove_listener(listener_id) || self.responders.remove_listener(listener_id)
+    }
+
+    pub(super) fn remove_listeners_by_kind(&mut self, event_kind: &K) -> usize {
+        self.listeners.remove_listeners_by_kind(event_kind) + self.responders.remove_listeners_by_kind(event_kind)
+    }
+
+    pub(super) fn register_listener<F>(&mut self, event_kind: &K, mut listener: F) -> Uuid
     where
         F: FnMut(T) + Send + Sync + 'static, {
+        self.register_fallible_listener(event_kind, move |arg| {
+            listener(arg);
+            Ok(())
+        })
+    }
+
+    pub(super) fn register_listener_with_priority<F>(
+        &mut self,
+        event_kind: &K,
+        priority: i32,
+        mut listener: F,
+    ) -> Uuid
+    where
+        F: FnMut(T) + Send + Sync + 'static, {
+        let listener = Listener::new(move |arg| {
+            listener(arg);
+            Ok(())
+        });
+
+        self.insert_listener(Uuid::new_v4(), event_kind, priority, listener)
+    }
+
+    /// Registers a listener which receives its past events before the events emitted by the other threads,
+    /// and returns it with its identifier and the guard holding back the other threads.
+    pub(super) fn register_gated_listener<F>(
+        &mut self,
+        event_kind: &K,
+        mut listener: F,
+    ) -> (Uuid, Listener<T>, GateGuard)
+    where
+        F: FnMut(T) + Send + Sync + 'static, {
+        let (listener, gate) = Listener::new_gated(move |arg| {
+            listener(arg);
+            Ok(())
+        });
+
+        let listener_id = self.insert_listener(Uuid::new_v4(), event_kind, DEFAULT_PRIORITY, listener.clone());
+
+        (listener_id, listener, gate)
+    }
+
+    pub(super) fn register_contextual_listener<F>(&mut self, event_kind: &K, priority: i32, mut listener: F) -> Uuid
+    where
+        F: FnMut(T, &EventContext) + Send + Sync + 'static, {
+        let listener = Listener::new_contextual(move |arg, context| {
+            listener(arg, context);
+            Ok(())
+        });
+
+        self.insert_listener(Uuid::new_v4(), event_kind, priority, listener)
+    }
+
+    pub(super) fn register_fallible_listener<F>(&mut self, event_kind: &K, listener: F) -> Uuid
+    where
+        F: FnMut(T) -> Result<()> + Send + Sync + 'static, {
+        self.insert_listener(Uuid::new_v4(), event_kind, DEFAULT_PRIORITY, Listener::new(listener))
+    }
+
+    /// Registers a listener which unregisters itself after `max_calls` calls.
+    pub(super) fn register_limited_listener<F>(
+        &mut self,
+        registry: Weak<RwLock<Self>>,
+        event_kind: &K,
+        max_calls: NonZeroUsize,
+        mut listener: F,
+    ) -> Uuid
+    where
+        F: FnMut(T) + Send + Sync + 'static, {
+        let mut remaining = max_calls.get();
+
+        self.register_expiring_listener(registry, event_kind, move |arg| {
+            remaining -= 1;
+            listener(arg);
+
+            remaining == 0
+        })
+    }
+
+    /// Registers a listener which unregisters itself as soon as it returns `true`.
+    ///
+    /// The calls are serialized by the listener lock, so the calls coming from a snapshot of the listeners
+    /// taken before the removal are ignored once the listener has expired.
+    pub(super) fn register_expiring_listener<F>(
+        &mut self,
+        registry: Weak<RwLock<Self>>,
+        event_kind: &K,
+        mut listener: F,
+    ) -> Uuid
+    where
+        F: FnMut(T) -> bool + Send + Sync + 'static, {
         let listener_id = Uuid::new_v4();
-        let event_kind = event_kind.to_string();
-        let entry = self.listeners.entry(event_kind.clone()).or_default();
+        let mut expired = false;
 
-        entry.insert(
+        self.insert_listener(
             listener_id,
+            event_kind,
+            DEFAULT_PRIORITY,
             Listener::new(move |arg| {
-                listener(arg);
+                if expired {
+                    return Ok(());
+                }
+
+                expired = listener(arg);
+
+                if expired && let Some(registry) = registry.upgrade() {
+                    Self::write_recovered(&registry).remove_listener(listener_id);
+                }
+
                 Ok(())
             }),
-        );
+        )
+    }
+
+    pub(super) fn register_responder<R, F>(&mut self, event_kind: &K, priority: i32, responder: F) -> Uuid
+    where
+        R: 'static,
+        F: Fn(T) -> R + Send + Sync + 'static, {
+        self.responders
+            .insert_listener(Uuid::new_v4(), event_kind, priority, Responder::new(responder))
+    }
+
+    pub(super) fn register_async_listener<F, Fut>(&mut self, event_kind: &K, listener: F) -> Uuid
+    where
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static, {
+        self.insert_listener(
+            Uuid::new_v4(),
+            event_kind,
+            DEFAULT_PRIORITY,
+            Listener::new_async(listener),
+        )
+    }
 
-        self.links.insert(listener_id, event_kind);
+    fn insert_listener(&mut self, listener_id: Uuid, event_kind: &K, priority: i32, listener: Listener<T>) -> Uuid {
+        self.listeners
+            .insert_listener(listener_id, event_kind, priority, listener)
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static, K: EventKey> Recoverable for ListenerRegistry<T, K> {
+    fn repair(&mut self) -> usize {
+        self.listeners.repair() + self.responders.repair()
+    }
 
-        listener_id
+    fn health_mut(&mut self) -> &mut HubHealth {
+        &mut self.health
     }
 }
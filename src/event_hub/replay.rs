@@ -0,0 +1,181 @@
+use crate::{EventKey, EventMetadata};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// The window of the past events of an event kind, which are replayed to the listeners added with history.
+///
+/// The window keeps at most `capacity` events, the oldest being dropped first,
+/// and with a maximum age, the events older than it are not replayed anymore.
+///
+/// # Example
+/// ```rust
+/// use emitix::event_hub::{EventHub, ReplayWindow};
+/// use std::time::Duration;
+///
+/// let manager = EventHub::default();
+/// manager
+///     .set_replay_window(
+///         "panel.log",
+///         ReplayWindow::last(100).with_max_age(Duration::from_secs(60)),
+///     )
+///     .unwrap();
+///
+/// manager.emit("panel.log", String::from("Started")).unwrap();
+/// manager
+///     .add_listener_with_history("panel.log", |line: String| println!("{line}"))
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReplayWindow {
+    capacity: usize,
+    max_age:  Option<Duration>,
+}
+
+impl ReplayWindow {
+    /// Creates a window keeping the last events of an event kind.
+    ///
+    /// # Arguments
+    /// - `capacity`: The maximum number of events kept in the window.
+    pub fn last(capacity: usize) -> Self {
+        Self {
+            capacity,
+            max_age: None,
+        }
+    }
+
+    /// Sets the maximum age of the events replayed from the window.
+    ///
+    /// # Arguments
+    /// - `max_age`: The duration after which an event is not replayed anymore.
+    pub fn with_max_age(self, max_age: Duration) -> Self {
+        Self {
+            max_age: Some(max_age),
+            ..self
+        }
+    }
+
+    /// Returns the maximum number of events kept in the window.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the maximum age of the events replayed from the window, if any.
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+
+    fn is_expired(&self, recorded_at: Instant) -> bool {
+        self.max_age.is_some_and(|max_age| recorded_at.elapsed() > max_age)
+    }
+}
+
+/// The past events of an event kind, with their sequence number, their recording time,
+/// and the metadata of their emission in envelope mode.
+struct ReplayBuffer<T> {
+    window: ReplayWindow,
+    events: VecDeque<(u64, Instant, Option<EventMetadata>, T)>,
+}
+
+impl<T> ReplayBuffer<T> {
+    /// Drops the events which don't fit in the window anymore.
+    fn trim(&mut self) {
+        while self.events.len() > self.window.capacity {
+            self.events.pop_front();
+        }
+
+        while let Some((_, recorded_at, ..)) = self.events.front()
+            && self.window.is_expired(*recorded_at)
+        {
+            self.events.pop_front();
+        }
+    }
+}
+
+/// The replay windows of the event kinds of a hub.
+pub(super) struct ReplayBuffers<T: Clone + Send + Sync + 'static, K: EventKey> {
+    buffers:  HashMap<K, ReplayBuffer<T>>,
+    next_seq: u64,
+}
+
+impl<T: Clone + Send + Sync + 'static, K: EventKey> ReplayBuffers<T, K> {
+    pub(super) fn new() -> Self {
+        Self {
+            buffers:  HashMap::new(),
+            next_seq: 0,
+        }
+    }
+
+    pub(super) fn window(&self, event_kind: &K) -> Option<ReplayWindow> {
+        self.buffers.get(event_kind).map(|buffer| buffer.window)
+    }
+
+    /// Sets the window of an event kind, keeping the events which still fit in it.
+    pub(super) fn set_window(&mut self, event_kind: K, window: ReplayWindow) {
+        let buffer = self.buffers.entry(event_kind).or_insert_with(|| ReplayBuffer {
+            window,
+            events: VecDeque::new(),
+        });
+
+        buffer.window = window;
+        buffer.trim();
+    }
+
+    pub(super) fn remove_window(&mut self, event_kind: &K) -> Option<ReplayWindow> {
+        self.buffers.remove(event_kind).map(|buffer| buffer.window)
+    }
+
+    /// Checks if no event kind has a window.
+    pub(super) fn is_empty(&self) -> bool {
+        self.buffers.is_empty()
+    }
+
+    /// Checks if one of the event kinds has a window.
+    pub(super) fn is_recording(&self, event_kinds: &[K]) -> bool {
+        !self.buffers.is_empty()
+            && event_kinds
+                .iter()
+                .any(|event_kind| self.buffers.contains_key(event_kind))
+    }
+
+    /// Records an event in the windows of the event kinds which have one, with the metadata of its emission.
+    pub(super) fn record(&mut self, event_kinds: &[K], event_arg: &T, metadata: Option<EventMetadata>) {
+        let seq = self.next_seq;
+        let now = Instant::now();
+
+        self.next_seq += 1;
+        for event_kind in event_kinds {
+            if let Some(buffer) = self.buffers.get_mut(event_kind) {
+                buffer.events.push_back((seq, now, metadata, event_arg.clone()));
+                buffer.trim();
+            }
+        }
+    }
+
+    /// Returns the events of the windows whose event kind is accepted by the filter, in emission order,
+    /// with the metadata of their emission.
+    pub(super) fn history<F: Fn(&K) -> bool>(&self, filter: F) -> Vec<(K, Option<EventMetadata>, T)> {
+        let mut history = self
+            .buffers
+            .iter()
+            .filter(|(event_kind, _)| filter(event_kind))
+            .flat_map(|(event_kind, buffer)| {
+                buffer
+                    .events
+                    .iter()
+                    .filter(|(_, recorded_at, ..)| !buffer.window.is_expired(*recorded_at))
+                    .map(move |(seq, _, metadata, event_arg)| (*seq, event_kind, *metadata, event_arg))
+            })
+            .collect::<Vec<_>>();
+
+        // An event emitted for several event kinds is replayed once.
+        history.sort_by_key(|(seq, ..)| *seq);
+        history.dedup_by_key(|(seq, ..)| *seq);
+        history
+            .into_iter()
+            .map(|(_, event_kind, metadata, event_arg)| (event_kind.clone(), metadata, event_arg.clone()))
+            .collect()
+    }
+}
@@ -0,0 +1,64 @@
+use crate::ListenerPanic;
+use anyhow::Result;
+use std::{
+    any::Any,
+    marker::PhantomData,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+type ResponderCallback<T, R> = Box<dyn Fn(T) -> R + Send + Sync>;
+
+/// A listener returning a value, whose type is erased so responders of any type can share a registry.
+pub(super) struct Responder<T: Clone + Send + Sync + 'static> {
+    callback:    Arc<dyn Any + Send + Sync>,
+    quarantined: Arc<AtomicBool>,
+    _arg:        PhantomData<fn(T)>,
+}
+
+impl<T: Clone + Send + Sync + 'static> Responder<T> {
+    pub(super) fn new<R: 'static, F: Fn(T) -> R + Send + Sync + 'static>(callback: F) -> Self {
+        let callback: ResponderCallback<T, R> = Box::new(callback);
+
+        Self {
+            callback:    Arc::new(callback),
+            quarantined: Arc::new(AtomicBool::new(false)),
+            _arg:        PhantomData,
+        }
+    }
+
+    pub(super) fn is_quarantined(&self) -> bool {
+        self.quarantined.load(Ordering::Acquire)
+    }
+
+    pub(super) fn set_quarantined(&self, quarantined: bool) {
+        self.quarantined.store(quarantined, Ordering::Release);
+    }
+
+    /// Calls the responder if it returns values of type `R`, or returns `None` otherwise.
+    ///
+    /// When `catch_panics` is set, a panic is caught and returned as a [`ListenerPanic`] error.
+    pub(super) fn respond<R: 'static>(&self, event_arg: T, catch_panics: bool) -> Option<Result<R>> {
+        let callback = self.callback.downcast_ref::<ResponderCallback<T, R>>()?;
+
+        Some(if catch_panics {
+            catch_unwind(AssertUnwindSafe(|| callback(event_arg)))
+                .map_err(|payload| ListenerPanic::from_payload(payload).into())
+        } else {
+            Ok(callback(event_arg))
+        })
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Clone for Responder<T> {
+    fn clone(&self) -> Self {
+        Self {
+            callback:    Arc::clone(&self.callback),
+            quarantined: Arc::clone(&self.quarantined),
+            _arg:        PhantomData,
+        }
+    }
+}
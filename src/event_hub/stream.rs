@@ -0,0 +1,38 @@
+use crate::Subscription;
+use futures::{channel::mpsc::Receiver, Stream, StreamExt};
+use uuid::Uuid;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A stream of the events emitted for one event kind.
+///
+/// It's created by [`EventHub::subscribe_stream`](super::EventHub::subscribe_stream).
+/// The underlying listener is removed from the hub when the stream is dropped.
+pub struct EventStream<T: Clone + Send + Sync + 'static> {
+    receiver:     Receiver<T>,
+    subscription: Subscription,
+}
+
+impl<T: Clone + Send + Sync + 'static> EventStream<T> {
+    pub(super) fn new(receiver: Receiver<T>, subscription: Subscription) -> Self {
+        Self {
+            receiver,
+            subscription,
+        }
+    }
+
+    /// Returns the identifier of the listener feeding this stream.
+    pub fn listener_id(&self) -> Uuid {
+        self.subscription.listener_id()
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Stream for EventStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.receiver.poll_next_unpin(cx)
+    }
+}
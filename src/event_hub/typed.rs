@@ -0,0 +1,296 @@
+use super::EventHub;
+use crate::{registry::Recoverable, EventEmitter, EventKey, EventManager, HubHealth, Result};
+use uuid::Uuid;
+use std::{
+    any::{type_name, Any, TypeId},
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+/// The only event kind of the hub storing the listeners of one event type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct EventType(&'static str);
+
+impl EventType {
+    fn of<E: 'static>() -> Self {
+        Self(type_name::<E>())
+    }
+}
+
+impl EventKey for EventType {
+    fn name(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// The hub of one event type, with its type erased.
+trait ErasedHub: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+
+    fn remove_listener(&self, listener_id: Uuid) -> Result<bool>;
+
+    fn clear_listeners(&self) -> Result<()>;
+}
+
+impl<E: Clone + Send + Sync + 'static> ErasedHub for EventHub<E, EventType> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn remove_listener(&self, listener_id: Uuid) -> Result<bool> {
+        EventManager::remove_listener(self, listener_id)
+    }
+
+    fn clear_listeners(&self) -> Result<()> {
+        EventManager::clear_listeners(self)
+    }
+}
+
+/// The hubs of the event types, indexed by the type identifier of their events.
+#[derive(Default)]
+struct TypedHubs {
+    hubs:   HashMap<TypeId, Arc<dyn ErasedHub>>,
+    health: HubHealth,
+}
+
+impl Recoverable for TypedHubs {
+    /// A hub is inserted at once, so a panic can't leave the map inconsistent.
+    fn repair(&mut self) -> usize {
+        0
+    }
+
+    fn health_mut(&mut self) -> &mut HubHealth {
+        &mut self.health
+    }
+}
+
+/// `TypedEventHub` is a thread-safe structure for managing events of different types.
+///
+/// The listeners are registered for an event type instead of an event kind,
+/// and each event is delivered to the listeners of its type.
+/// Each event type is managed by its own [`EventHub`].
+///
+/// # Example
+/// ```rust
+/// use emitix::event_hub::TypedEventHub;
+/// use std::sync::{Arc, Mutex};
+///
+/// #[derive(Clone)]
+/// struct UserCreated {
+///     name: String,
+/// }
+///
+/// #[derive(Clone)]
+/// struct OrderPlaced(u32);
+///
+/// let manager = TypedEventHub::new();
+/// let users = Arc::new(Mutex::new(Vec::new()));
+/// let orders = Arc::new(Mutex::new(Vec::new()));
+///
+/// let created = Arc::clone(&users);
+/// let user_listener = manager
+///     .add_listener(move |event: UserCreated| created.lock().unwrap().push(event.name))
+///     .unwrap();
+/// let placed = Arc::clone(&orders);
+/// manager
+///     .add_listener(move |event: OrderPlaced| placed.lock().unwrap().push(event.0))
+///     .unwrap();
+///
+/// manager
+///     .emit(UserCreated {
+///         name: String::from("Alice"),
+///     })
+///     .unwrap();
+/// manager.emit(OrderPlaced(42)).unwrap();
+///
+/// assert_eq!(*users.lock().unwrap(), ["Alice"]);
+/// assert_eq!(*orders.lock().unwrap(), [42]);
+///
+/// // The listeners are removed whatever the type of their events.
+/// assert!(manager.remove_listener(user_listener).unwrap());
+/// assert!(!manager.has_listeners::<UserCreated>().unwrap());
+/// assert_eq!(manager.listeners_count::<OrderPlaced>().unwrap(), 1);
+///
+/// manager.clear_listeners().unwrap();
+/// manager.emit(OrderPlaced(43)).unwrap();
+/// assert!(!manager.has_listeners::<OrderPlaced>().unwrap());
+/// assert_eq!(*orders.lock().unwrap(), [42]);
+/// ```
+#[derive(Clone, Default)]
+pub struct TypedEventHub {
+    hubs: Arc<RwLock<TypedHubs>>,
+}
+
+impl TypedEventHub {
+    /// Creates a new instance of `TypedEventHub`.
+    ///
+    /// # Returns
+    /// A new, empty instance of `TypedEventHub` ready to register listeners.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emits an event to all the listeners registered for its type.
+    ///
+    /// # Arguments
+    /// - `event`: The event passed to each listener.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the event was successfully emitted.
+    /// - `Err(EmitixError::LockPoisoned)` if access to the `Mutex` failed.
+    /// - `Err(EmitixError::ListenerFailures)` if some listeners returned an error.
+    pub fn emit<E: Clone + Send + Sync + 'static>(&self, event: E) -> Result<()> {
+        match self.existing_hub::<E>() {
+            Some(hub) => hub.emit(&EventType::of::<E>(), event),
+            None => Ok(()),
+        }
+    }
+
+    /// Adds an event listener for the events of type `E`.
+    ///
+    /// # Arguments
+    /// - `listener`: A function or closure that will be called when an event of type `E` is emitted.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the listener.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn add_listener<E, F>(&self, listener: F) -> Result<Uuid>
+    where
+        E: Clone + Send + Sync + 'static,
+        F: FnMut(E) + Send + Sync + 'static, {
+        self.hub::<E>().add_listener(&EventType::of::<E>(), listener)
+    }
+
+    /// Adds an event listener which can fail for the events of type `E`.
+    ///
+    /// The errors returned by the listener are reported by `emit` and by the emitters of the hub.
+    ///
+    /// # Arguments
+    /// - `listener`: A function or closure that will be called when an event of type `E` is emitted.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the listener.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn add_fallible_listener<E, F>(&self, listener: F) -> Result<Uuid>
+    where
+        E: Clone + Send + Sync + 'static,
+        F: FnMut(E) -> anyhow::Result<()> + Send + Sync + 'static, {
+        self.hub::<E>().add_fallible_listener(&EventType::of::<E>(), listener)
+    }
+
+    /// Removes a listener, whatever the type of its events.
+    ///
+    /// # Arguments
+    /// - `listener_id`: A unique identifier for the listener to be removed.
+    ///
+    /// # Returns
+    /// - `Ok(bool)` indicating whether the listener was registered.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn remove_listener(&self, listener_id: Uuid) -> Result<bool> {
+        for hub in self.hubs() {
+            if hub.remove_listener(listener_id)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Checks if there are any listeners for the events of type `E`.
+    ///
+    /// # Returns
+    /// - `Ok(bool)` indicating whether there are listeners for the events of type `E`.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn has_listeners<E: Clone + Send + Sync + 'static>(&self) -> Result<bool> {
+        match self.existing_hub::<E>() {
+            Some(hub) => hub.has_listeners(&EventType::of::<E>()),
+            None => Ok(false),
+        }
+    }
+
+    /// Returns the number of listeners for the events of type `E`.
+    ///
+    /// # Returns
+    /// - `Ok(usize)` representing the number of listeners for the events of type `E`.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn listeners_count<E: Clone + Send + Sync + 'static>(&self) -> Result<usize> {
+        match self.existing_hub::<E>() {
+            Some(hub) => hub.listeners_count(&EventType::of::<E>()),
+            None => Ok(0),
+        }
+    }
+
+    /// Clears all listeners, whatever the type of their events.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the listeners were successfully cleared.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    pub fn clear_listeners(&self) -> Result<()> {
+        for hub in self.hubs() {
+            hub.clear_listeners()?;
+        }
+
+        Ok(())
+    }
+
+    /// Creates a new event emitter for the events of type `E`.
+    ///
+    /// # Returns
+    /// - `Ok(Box<dyn EventEmitter<E>>)` which is a boxed trait object that implements the `EventEmitter` trait.
+    /// - `Err(EmitixError)` if access to the `Mutex` failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use emitix::event_hub::TypedEventHub;
+    /// use std::sync::{
+    ///     atomic::{AtomicU32, Ordering},
+    ///     Arc,
+    /// };
+    ///
+    /// #[derive(Clone)]
+    /// struct OrderPlaced(u32);
+    ///
+    /// let manager = TypedEventHub::new();
+    /// let total = Arc::new(AtomicU32::new(0));
+    ///
+    /// let placed = Arc::clone(&total);
+    /// manager
+    ///     .add_listener(move |event: OrderPlaced| {
+    ///         placed.fetch_add(event.0, Ordering::SeqCst);
+    ///     })
+    ///     .unwrap();
+    ///
+    /// let emitter = manager.new_emitter::<OrderPlaced>().unwrap();
+    /// emitter.emit(OrderPlaced(42)).unwrap();
+    ///
+    /// assert_eq!(total.load(Ordering::SeqCst), 42);
+    /// ```
+    pub fn new_emitter<E: Clone + Send + Sync + 'static>(&self) -> Result<Box<dyn EventEmitter<E>>> {
+        Ok(self.hub::<E>().new_emitter(&EventType::of::<E>()))
+    }
+
+    fn hubs(&self) -> Vec<Arc<dyn ErasedHub>> {
+        TypedHubs::read_recovered(&self.hubs).hubs.values().cloned().collect()
+    }
+
+    fn existing_hub<E: Clone + Send + Sync + 'static>(&self) -> Option<EventHub<E, EventType>> {
+        TypedHubs::read_recovered(&self.hubs)
+            .hubs
+            .get(&TypeId::of::<E>())
+            .and_then(|hub| Self::downcast(hub.as_ref()))
+    }
+
+    fn hub<E: Clone + Send + Sync + 'static>(&self) -> EventHub<E, EventType> {
+        let mut typed_hubs = TypedHubs::write_recovered(&self.hubs);
+        let hub = typed_hubs
+            .hubs
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| Arc::new(EventHub::<E, EventType>::default()));
+
+        Self::downcast(hub.as_ref()).expect("The hubs are stored under the type identifier of their events")
+    }
+
+    fn downcast<E: Clone + Send + Sync + 'static>(hub: &dyn ErasedHub) -> Option<EventHub<E, EventType>> {
+        hub.as_any().downcast_ref::<EventHub<E, EventType>>().cloned()
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{EmitixError, Result, Subscription};
+use futures::{channel::oneshot::Receiver, FutureExt};
+use uuid::Uuid;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A future resolving with the next matching event emitted for one event kind.
+///
+/// It's created by [`EventHub::wait_for`](super::EventHub::wait_for)
+/// and [`EventHub::wait_for_matching`](super::EventHub::wait_for_matching).
+/// The underlying listener is removed from the hub when the future is dropped.
+pub struct EventWait<T: Clone + Send + Sync + 'static> {
+    receiver:     Receiver<T>,
+    subscription: Subscription,
+    event_kind:   String,
+}
+
+impl<T: Clone + Send + Sync + 'static> EventWait<T> {
+    pub(super) fn new(receiver: Receiver<T>, subscription: Subscription, event_kind: String) -> Self {
+        Self {
+            receiver,
+            subscription,
+            event_kind,
+        }
+    }
+
+    /// Returns the identifier of the listener waiting for the event.
+    pub fn listener_id(&self) -> Uuid {
+        self.subscription.listener_id()
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Future for EventWait<T> {
+    type Output = Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<T>> {
+        self.receiver.poll_unpin(cx).map(|res| {
+            res.map_err(|_| EmitixError::NoListeners {
+                kind: self.event_kind.clone(),
+            })
+        })
+    }
+}
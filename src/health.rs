@@ -0,0 +1,72 @@
+/// A report about the recoveries of the shared registry of an event manager.
+///
+/// When a thread panics while it holds the lock of the registry, the lock is poisoned.
+/// Instead of failing from then on, the manager checks and repairs the registry, then clears the poisoning.
+///
+/// # Example
+/// ```rust
+/// use emitix::event_hub::EventHub;
+///
+/// let manager = EventHub::<()>::default();
+/// let health = manager.health();
+///
+/// assert!(!health.recovered());
+/// ```
+///
+/// A registry poisoned in the middle of a modification is repaired, and the manager keeps working:
+/// ```rust
+/// use emitix::{event_hub::EventHub, EventKey, EventManager};
+/// use std::{
+///     hash::{Hash, Hasher},
+///     sync::atomic::{AtomicBool, Ordering},
+///     thread,
+/// };
+///
+/// static BROKEN: AtomicBool = AtomicBool::new(false);
+///
+/// /// An event kind whose hashing panics while it's broken.
+/// #[derive(Debug, Clone, PartialEq, Eq)]
+/// struct Kind(&'static str);
+///
+/// impl Hash for Kind {
+///     fn hash<H: Hasher>(&self, state: &mut H) {
+///         assert!(!BROKEN.load(Ordering::SeqCst), "Broken event kind");
+///         self.0.hash(state);
+///     }
+/// }
+///
+/// impl EventKey for Kind {}
+///
+/// let manager = EventHub::<u32, Kind>::default();
+/// let listener_id = manager.add_listener(&Kind("tick"), |_| ()).unwrap();
+///
+/// // The removal panics after the link of the listener was removed, but before the listener itself.
+/// BROKEN.store(true, Ordering::SeqCst);
+/// let hub = manager.clone();
+/// assert!(thread::spawn(move || hub.remove_listener(listener_id)).join().is_err());
+/// BROKEN.store(false, Ordering::SeqCst);
+///
+/// let health = manager.health();
+/// assert_eq!(health.poison_recoveries, 1);
+/// assert_eq!(health.repaired_entries, 1);
+///
+/// manager.add_listener(&Kind("tock"), |_| ()).unwrap();
+/// manager.emit(&Kind("tick"), 1).unwrap();
+/// assert_eq!(manager.listeners_count(&Kind("tick")).unwrap(), 1);
+/// assert!(manager.remove_listener(listener_id).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HubHealth {
+    /// The number of times the registry was recovered from a poisoned lock.
+    pub poison_recoveries: usize,
+    /// The number of inconsistent entries repaired during the recoveries.
+    pub repaired_entries:  usize,
+}
+
+impl HubHealth {
+    /// Checks if the registry was recovered at least once.
+    pub fn recovered(&self) -> bool {
+        self.poison_recoveries > 0
+    }
+}
@@ -0,0 +1,78 @@
+use crate::pattern;
+use std::{fmt::Debug, hash::Hash};
+
+/// The type of the keys identifying the event kinds of an event manager.
+///
+/// It's implemented for `String`, whose keys can be patterns and form a hierarchy whose levels
+/// are separated by dots. It can be implemented for an enum of the event kinds,
+/// so a misspelled event kind is a compilation error instead of a kind without listeners.
+///
+/// # Example
+/// ```rust
+/// use emitix::{event_hub::EventHub, EventKey, EventManager};
+///
+/// #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// enum OrderEvent {
+///     Placed,
+///     Shipped,
+/// }
+///
+/// impl EventKey for OrderEvent {}
+///
+/// let manager = EventHub::<u32, OrderEvent>::default();
+/// manager
+///     .add_listener(&OrderEvent::Placed, |order| {
+///         println!("Order {order} placed")
+///     })
+///     .unwrap();
+///
+/// manager.emit(&OrderEvent::Placed, 42).unwrap();
+/// assert!(!manager.has_listeners(&OrderEvent::Shipped).unwrap());
+/// ```
+pub trait EventKey: Eq + Hash + Clone + Debug + Send + Sync + 'static {
+    /// Checks if the key is a pattern matching other keys.
+    ///
+    /// By default, a key is not a pattern.
+    fn is_pattern(&self) -> bool {
+        false
+    }
+
+    /// Checks if the key, which is a pattern, matches another key.
+    ///
+    /// By default, a key matches no other key.
+    fn matches(&self, _event_kind: &Self) -> bool {
+        false
+    }
+
+    /// Returns the parent of the key, to which the events bubble up.
+    ///
+    /// By default, the keys have no parent.
+    fn parent(&self) -> Option<Self> {
+        None
+    }
+
+    /// Returns the name of the key, used in the error reports.
+    ///
+    /// By default, it's the debug representation of the key.
+    fn name(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+impl EventKey for String {
+    fn is_pattern(&self) -> bool {
+        pattern::is_pattern(self)
+    }
+
+    fn matches(&self, event_kind: &Self) -> bool {
+        pattern::matches(self, event_kind)
+    }
+
+    fn parent(&self) -> Option<Self> {
+        pattern::parent_kind(self).map(str::to_string)
+    }
+
+    fn name(&self) -> String {
+        self.clone()
+    }
+}
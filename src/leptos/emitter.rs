@@ -1,13 +1,12 @@
-use crate::EventEmitter;
-use anyhow::Result;
+use crate::{EventEmitter, Result};
 use leptos::callback::{Callback, Callable};
 
 pub(super) struct LeptosChannelEmitter<T: Clone + Send + Sync + 'static> {
-    callback: Callback<T>,
+    callback: Callback<T, Result<()>>,
 }
 
 impl<T: Clone + Send + Sync + 'static> LeptosChannelEmitter<T> {
-    pub(super) fn new(callback: Callback<T>) -> Self {
+    pub(super) fn new(callback: Callback<T, Result<()>>) -> Self {
         Self {
             callback,
         }
@@ -16,14 +15,12 @@ impl<T: Clone + Send + Sync + 'static> LeptosChannelEmitter<T> {
 
 impl<T: Clone + Send + Sync + 'static> EventEmitter<T> for LeptosChannelEmitter<T> {
     fn emit(&self, event_arg: T) -> Result<()> {
-        self.callback.run(event_arg);
-
-        Ok(())
+        self.callback.run(event_arg)
     }
 
     fn clone(&self) -> Box<dyn EventEmitter<T>> {
         Box::new(Self {
-            callback: self.callback.clone(),
+            callback: self.callback,
         })
     }
 }
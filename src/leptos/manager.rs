@@ -1,10 +1,12 @@
 use super::{emitter::LeptosChannelEmitter, registry::ListenerRegistry};
-use crate::{EventEmitter, EventManager};
-use anyhow::{Result, Error};
+use crate::{registry::Recoverable, EmitixError, EventEmitter, EventManager, HubHealth, ListenerFailure, Result};
 use uuid::Uuid;
-use log::error;
 use leptos::callback::{Callback, Callable};
-use std::sync::{Arc, RwLock};
+use std::{
+    num::NonZeroUsize,
+    slice,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
 
 #[derive(Clone)]
 pub struct LeptosEventChannels<T: Clone + Send + Sync + 'static = ()> {
@@ -16,25 +18,53 @@ impl<T: Clone + Send + Sync + 'static> LeptosEventChannels<T> {
         Self::default()
     }
 
-    pub fn emit(&self, event_kind: &str, event_arg: T) -> Result<()> {
-        let callbacks = {
-            let registry = self
-                .registry
-                .read()
-                .map_err(|err| Error::msg(format!("Mutex lock failed in Leptos event channels: {err}")))?;
+    pub fn emit<Q: ?Sized + ToOwned<Owned = String>>(&self, event_kind: &Q, event_arg: T) -> Result<()> {
+        Self::dispatch(&self.registry, Some(&[event_kind.to_owned()]), event_arg)
+    }
+
+    pub fn health(&self) -> HubHealth {
+        self.read_registry().health()
+    }
+
+    fn read_registry(&self) -> RwLockReadGuard<'_, ListenerRegistry<T>> {
+        ListenerRegistry::read_recovered(&self.registry)
+    }
+
+    fn write_registry(&self) -> RwLockWriteGuard<'_, ListenerRegistry<T>> {
+        ListenerRegistry::write_recovered(&self.registry)
+    }
+
+    /// Calls the listeners reached by some event kinds, or all the listeners with `None`,
+    /// and reports the failures of the listeners like `EventHub` does.
+    fn dispatch(registry: &RwLock<ListenerRegistry<T>>, event_kinds: Option<&[String]>, event_arg: T) -> Result<()> {
+        let listeners = {
+            let registry = ListenerRegistry::read_recovered(registry);
 
             registry
-                .listeners()
-                .get(event_kind)
-                .map(|list| list.values().cloned().collect::<Vec<_>>())
-                .unwrap_or_default()
+                .matching_listeners(event_kinds)
+                .into_iter()
+                .map(|(event_kind, entry)| (entry.id, event_kind.clone(), entry.listener))
+                .collect::<Vec<_>>()
         };
 
-        for callback in callbacks {
-            callback.run(event_arg.clone());
+        let failures = listeners
+            .into_iter()
+            .filter_map(|(id, kind, callback)| {
+                let source = callback.run(event_arg.clone()).err()?;
+
+                Some(ListenerFailure {
+                    id,
+                    kind,
+                    source,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(EmitixError::ListenerFailures(failures))
         }
-
-        Ok(())
     }
 }
 
@@ -48,143 +78,122 @@ impl<T: Clone + Send + Sync + 'static> Default for LeptosEventChannels<T> {
 
 impl<T: Clone + Send + Sync + 'static> EventManager<T> for LeptosEventChannels<T> {
     fn list_event_kinds(&self) -> Result<Vec<String>> {
-        let registry = self
-            .registry
-            .read()
-            .map_err(|err| Error::msg(format!("Mutex lock failed in Leptos event channels: {err}")))?;
+        let registry = self.read_registry();
 
         Ok(registry.listeners().keys().cloned().collect::<Vec<_>>())
     }
 
-    fn has_listeners(&self, event_kind: &str) -> Result<bool> {
-        let registry = self
-            .registry
-            .read()
-            .map_err(|err| Error::msg(format!("Mutex lock failed in Leptos event channels: {err}")))?;
+    fn has_listeners<Q: ?Sized + ToOwned<Owned = String>>(&self, event_kind: &Q) -> Result<bool> {
+        let registry = self.read_registry();
 
-        Ok(registry.listeners().contains_key(event_kind))
+        Ok(!registry.matching_listeners(Some(&[event_kind.to_owned()])).is_empty())
     }
 
-    fn listeners_count(&self, event_kind: &str) -> Result<usize> {
-        let registry = self
-            .registry
-            .read()
-            .map_err(|err| Error::msg(format!("Mutex lock failed in Leptos event channels: {err}")))?;
+    fn listeners_count<Q: ?Sized + ToOwned<Owned = String>>(&self, event_kind: &Q) -> Result<usize> {
+        let registry = self.read_registry();
 
-        Ok(registry.listeners().get(event_kind).map_or(0, |l| l.len()))
+        Ok(registry.matching_listeners(Some(&[event_kind.to_owned()])).len())
     }
 
     fn clear_listeners(&self) -> Result<()> {
-        let mut registry = self
-            .registry
-            .write()
-            .map_err(|err| Error::msg(format!("Mutex lock failed in Leptos event channels: {err}")))?;
+        let mut registry = self.write_registry();
 
         registry.clear();
         Ok(())
     }
 
-    fn add_listener<F: FnMut(T) + Send + Sync + 'static>(&self, event_kind: &str, listener: F) -> Result<Uuid> {
-        let mut registry = self
-            .registry
-            .write()
-            .map_err(|err| Error::msg(format!("Mutex lock failed in Leptos event channels: {err}")))?;
+    fn add_listener<Q: ?Sized + ToOwned<Owned = String>, F: FnMut(T) + Send + Sync + 'static>(
+        &self,
+        event_kind: &Q,
+        listener: F,
+    ) -> Result<Uuid> {
+        let mut registry = self.write_registry();
+
+        Ok(registry.register_listener(&event_kind.to_owned(), listener))
+    }
+
+    fn add_listener_with_priority<Q: ?Sized + ToOwned<Owned = String>, F: FnMut(T) + Send + Sync + 'static>(
+        &self,
+        event_kind: &Q,
+        priority: i32,
+        listener: F,
+    ) -> Result<Uuid> {
+        let mut registry = self.write_registry();
+
+        Ok(registry.register_listener_with_priority(&event_kind.to_owned(), priority, listener))
+    }
+
+    fn add_fallible_listener<
+        Q: ?Sized + ToOwned<Owned = String>,
+        F: FnMut(T) -> anyhow::Result<()> + Send + Sync + 'static,
+    >(
+        &self,
+        event_kind: &Q,
+        listener: F,
+    ) -> Result<Uuid> {
+        let mut registry = self.write_registry();
+
+        Ok(registry.register_fallible_listener(&event_kind.to_owned(), listener))
+    }
 
-        Ok(registry.register_listener(event_kind, listener))
+    fn add_listener_limited<Q: ?Sized + ToOwned<Owned = String>, F: FnMut(T) + Send + Sync + 'static>(
+        &self,
+        event_kind: &Q,
+        max_calls: usize,
+        listener: F,
+    ) -> Result<Uuid> {
+        let max_calls = NonZeroUsize::new(max_calls)
+            .ok_or_else(|| EmitixError::invalid_argument("max_calls", "must not be zero"))?;
+        let mut registry = self.write_registry();
+
+        Ok(registry.register_limited_listener(
+            Arc::downgrade(&self.registry),
+            &event_kind.to_owned(),
+            max_calls,
+            listener,
+        ))
     }
 
     fn remove_listener(&self, listener_id: Uuid) -> Result<bool> {
-        let mut registry = self
-            .registry
-            .write()
-            .map_err(|err| Error::msg(format!("Mutex lock failed in Leptos event channels: {err}")))?;
+        let mut registry = self.write_registry();
 
         Ok(registry.remove_listener(listener_id))
     }
 
-    fn remove_listeners_by_kind(&self, event_kind: &str) -> Result<usize> {
-        let mut registry = self
-            .registry
-            .write()
-            .map_err(|err| Error::msg(format!("Mutex lock failed in Leptos event channels: {err}")))?;
+    fn remove_listeners_by_kind<Q: ?Sized + ToOwned<Owned = String>>(&self, event_kind: &Q) -> Result<usize> {
+        let mut registry = self.write_registry();
 
-        Ok(registry.remove_listeners_by_kind(event_kind))
+        Ok(registry.remove_listeners_by_kind(&event_kind.to_owned()))
     }
 
-    fn new_emitter(&self, event_kind: &str) -> Box<dyn EventEmitter<T>> {
-        let event_kind = event_kind.to_string();
+    fn new_emitter<Q: ?Sized + ToOwned<Owned = String>>(&self, event_kind: &Q) -> Box<dyn EventEmitter<T>> {
+        let event_kind = event_kind.to_owned();
         let registry = Arc::clone(&self.registry);
 
-        let callback = Callback::new(move |event_arg: T| {
-            let callbacks = {
-                let registry = match registry.read() {
-                    Ok(lock) => lock,
-                    Err(err) => {
-                        error!("Failed to lock the registry in Leptos event channels for kind `{event_kind}`: {err}.");
-                        return;
-                    }
-                };
-
-                registry
-                    .listeners()
-                    .get(&event_kind)
-                    .map(|listeners| listeners.values().cloned().collect::<Vec<_>>())
-                    .unwrap_or_default()
-            };
-
-            for callback in callbacks {
-                callback.run(event_arg.clone());
-            }
-        });
+        let callback =
+            Callback::new(move |event_arg: T| Self::dispatch(&registry, Some(slice::from_ref(&event_kind)), event_arg));
 
         Box::new(LeptosChannelEmitter::new(callback))
     }
 
-    fn new_broadcast_emitter(&self, event_kinds: &[&str]) -> Box<dyn EventEmitter<T>> {
+    fn new_broadcast_emitter<Q: ?Sized + ToOwned<Owned = String>>(
+        &self,
+        event_kinds: &[&Q],
+    ) -> Box<dyn EventEmitter<T>> {
         let event_kinds = if event_kinds.is_empty() {
             None
         } else {
-            Some(event_kinds.iter().map(|&s| s.to_string()).collect::<Vec<_>>())
+            Some(event_kinds.iter().map(|&kind| kind.to_owned()).collect::<Vec<_>>())
         };
 
-        let kind_list = event_kinds.as_ref().map(|v| v.join(", ")).unwrap_or_default();
         let registry = Arc::clone(&self.registry);
 
-        let callback = Callback::new(move |event_arg: T| {
-            let callbacks = {
-                let registry = match registry.read() {
-                    Ok(lock) => lock,
-                    Err(err) => {
-                        error!("Failed to lock the registry in Leptos event channels for kinds {kind_list}: {err}.");
-                        return;
-                    }
-                };
-
-                let listeners = registry.listeners();
-                let kinds_to_process = match &event_kinds {
-                    Some(list) => list.clone(),
-                    None => listeners.keys().cloned().collect::<Vec<_>>(),
-                };
-
-                let mut event_listeners = Vec::new();
-                for event_kind in kinds_to_process {
-                    if let Some(callbacks) = listeners.get(&event_kind) {
-                        event_listeners.extend(callbacks.values().cloned());
-                    }
-                }
-
-                event_listeners
-            };
-
-            for callback in callbacks {
-                callback.run(event_arg.clone());
-            }
-        });
+        let callback = Callback::new(move |event_arg: T| Self::dispatch(&registry, event_kinds.as_deref(), event_arg));
 
         Box::new(LeptosChannelEmitter::new(callback))
     }
 
     fn new_null_emitter() -> Box<dyn EventEmitter<T>> {
-        Box::new(LeptosChannelEmitter::new(Callback::new(|_| {})))
+        Box::new(LeptosChannelEmitter::new(Callback::new(|_| Ok(()))))
     }
 }
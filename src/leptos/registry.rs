@@ -1,81 +1,181 @@
+use crate::{
+    registry::{ListenerEntry, ListenerMap, Recoverable, RegistryCore, DEFAULT_PRIORITY},
+    HubHealth,
+};
+use anyhow::{Error, Result};
 use uuid::Uuid;
 use leptos::callback::Callback;
 use std::{
-    collections::HashMap,
-    sync::{Arc, RwLock},
+    num::NonZeroUsize,
+    sync::{Arc, RwLock, Weak},
 };
 
-pub(super) type ListenerMap<T> = HashMap<String, HashMap<Uuid, Callback<T>>>;
+/// The callback of a listener, returning the error of the listener.
+pub(super) type ListenerCallback<T> = Callback<T, Result<()>>;
 
 pub(super) struct ListenerRegistry<T: Clone + Send + Sync + 'static> {
-    listeners: ListenerMap<T>,
-    links:     HashMap<Uuid, String>,
+    listeners: RegistryCore<ListenerCallback<T>, String>,
+    health:    HubHealth,
 }
 
 impl<T: Clone + Send + Sync + 'static> ListenerRegistry<T> {
     pub(super) fn new() -> Self {
         Self {
-            listeners: HashMap::new(),
-            links:     HashMap::new(),
+            listeners: RegistryCore::new(),
+            health:    HubHealth::default(),
         }
     }
 
     pub(super) fn clear(&mut self) {
         self.listeners.clear();
-        self.links.clear();
     }
 
-    pub(super) fn listeners(&self) -> &ListenerMap<T> {
-        &self.listeners
+    pub(super) fn health(&self) -> HubHealth {
+        self.health
     }
 
-    pub(super) fn remove_listener(&mut self, listener_id: Uuid) -> bool {
-        if let Some(event_kind) = self.links.remove(&listener_id) {
-            if let Some(listeners) = self.listeners.get_mut(&event_kind) {
-                listeners.remove(&listener_id);
-                if listeners.is_empty() {
-                    self.listeners.remove(&event_kind);
-                }
-
-                return true;
-            }
-        }
+    pub(super) fn listeners(&self) -> &ListenerMap<ListenerCallback<T>, String> {
+        self.listeners.listeners()
+    }
+
+    pub(super) fn matching_listeners(
+        &self,
+        event_kinds: Option<&[String]>,
+    ) -> Vec<(&String, &ListenerEntry<ListenerCallback<T>>)> {
+        self.listeners.matching_listeners(event_kinds)
+    }
 
-        false
+    pub(super) fn remove_listener(&mut self, listener_id: Uuid) -> bool {
+        self.listeners.remove_listener(listener_id)
     }
 
     pub(super) fn remove_listeners_by_kind(&mut self, event_kind: &str) -> usize {
-        match self.listeners.remove(event_kind) {
-            None => 0,
-            Some(listeners) => {
-                let sz = listeners.len();
-                for listener_id in listeners.keys() {
-                    self.links.remove(listener_id);
-                }
-
-                sz
-            }
-        }
+        self.listeners.remove_listeners_by_kind(&event_kind.to_string())
     }
 
     pub(super) fn register_listener<F>(&mut self, event_kind: &str, listener: F) -> Uuid
     where
         F: FnMut(T) + Send + Sync + 'static, {
-        let f = Arc::new(RwLock::new(listener));
-        let receiver = Callback::new(move |arg| {
-            let f = Arc::clone(&f);
-            if let Ok(mut caller) = f.write() {
-                caller(arg);
+        self.register_listener_with_priority(event_kind, DEFAULT_PRIORITY, listener)
+    }
+
+    pub(super) fn register_listener_with_priority<F>(
+        &mut self,
+        event_kind: &str,
+        priority: i32,
+        mut listener: F,
+    ) -> Uuid
+    where
+        F: FnMut(T) + Send + Sync + 'static, {
+        self.insert_listener(Uuid::new_v4(), event_kind, priority, move |arg| {
+            listener(arg);
+            Ok(())
+        })
+    }
+
+    pub(super) fn register_fallible_listener<F>(&mut self, event_kind: &str, listener: F) -> Uuid
+    where
+        F: FnMut(T) -> Result<()> + Send + Sync + 'static, {
+        self.insert_listener(Uuid::new_v4(), event_kind, DEFAULT_PRIORITY, listener)
+    }
+
+    /// Registers a listener which unregisters itself after `max_calls` calls.
+    ///
+    /// The calls are serialized by the listener lock, so the calls coming from a snapshot of the listeners
+    /// taken before the removal are ignored once the budget is exhausted.
+    pub(super) fn register_limited_listener<F>(
+        &mut self,
+        registry: Weak<RwLock<Self>>,
+        event_kind: &str,
+        max_calls: NonZeroUsize,
+        mut listener: F,
+    ) -> Uuid
+    where
+        F: FnMut(T) + Send + Sync + 'static, {
+        let listener_id = Uuid::new_v4();
+        let mut remaining = max_calls.get();
+
+        self.insert_listener(listener_id, event_kind, DEFAULT_PRIORITY, move |arg| {
+            if remaining == 0 {
+                return Ok(());
+            }
+
+            remaining -= 1;
+            listener(arg);
+
+            if remaining == 0
+                && let Some(registry) = registry.upgrade()
+            {
+                Self::write_recovered(&registry).remove_listener(listener_id);
             }
+
+            Ok(())
+        })
+    }
+
+    /// Wraps a listener in a Leptos callback, which serializes its calls.
+    fn insert_listener<F>(&mut self, listener_id: Uuid, event_kind: &str, priority: i32, listener: F) -> Uuid
+    where
+        F: FnMut(T) -> Result<()> + Send + Sync + 'static, {
+        let f = Arc::new(RwLock::new(listener));
+        let callback = Callback::new(move |arg| match f.write() {
+            Ok(mut caller) => caller(arg),
+            Err(e) => Err(Error::msg(format!("Failed to lock listener callback: {e}"))),
         });
 
-        let listener_id = Uuid::new_v4();
-        let event_kind = event_kind.to_string();
-        let entry = self.listeners.entry(event_kind.clone()).or_default();
+        self.listeners
+            .insert_listener(listener_id, &event_kind.to_string(), priority, callback)
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Recoverable for ListenerRegistry<T> {
+    fn repair(&mut self) -> usize {
+        self.listeners.repair()
+    }
+
+    fn health_mut(&mut self) -> &mut HubHealth {
+        &mut self.health
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ListenerRegistry;
+    use crate::registry::Recoverable;
+    use std::{
+        sync::{Arc, RwLock},
+        thread,
+    };
+
+    #[test]
+    fn write_recovered_clears_the_poisoning() {
+        let lock = Arc::new(RwLock::new(ListenerRegistry::<u32>::new()));
+        let poisoning = Arc::clone(&lock);
+        let poisoned = thread::spawn(move || {
+            let mut registry = poisoning.write().unwrap();
+
+            registry.register_listener("user.created", |_| ());
+            panic!("Interrupted modification");
+        });
+
+        assert!(poisoned.join().is_err());
+        assert!(lock.is_poisoned());
 
-        entry.insert(listener_id, receiver);
-        self.links.insert(listener_id, event_kind);
+        let listener_id = ListenerRegistry::write_recovered(&lock).register_listener("user.deleted", |_| ());
+        let registry = ListenerRegistry::read_recovered(&lock);
 
-        listener_id
+        assert!(!lock.is_poisoned());
+        assert_eq!(registry.health().poison_recoveries, 1);
+        assert_eq!(registry.health().repaired_entries, 0);
+        assert_eq!(
+            registry.matching_listeners(Some(&[String::from("user.created")])).len(),
+            1
+        );
+        assert_eq!(
+            registry.matching_listeners(Some(&[String::from("user.deleted")]))[0]
+                .1
+                .id,
+            listener_id
+        );
     }
 }
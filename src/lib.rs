@@ -1,4 +1,12 @@
 //! `emitix` - A thread-safe event management library
+mod envelope;
+mod error;
+mod event;
+mod health;
+mod key;
+mod pattern;
+mod registry;
+mod subscription;
 mod traits;
 
 pub mod event_hub;
@@ -6,4 +14,20 @@ pub mod event_hub;
 #[cfg(feature = "leptos")]
 pub mod leptos;
 
-pub use traits::{EventEmitter, EventManager};
+pub use self::{
+    envelope::{Envelope, EventMetadata},
+    error::{AsyncListenerSkipped, EmitixError, ErrorReport, FailureReport, ListenerFailure, ListenerPanic, Result},
+    event::Event,
+    health::HubHealth,
+    key::EventKey,
+    subscription::{Subscription, Subscriptions},
+    traits::{EventEmitter, EventManager},
+};
+
+#[cfg(feature = "derive")]
+pub use emitix_derive::{listeners, Event};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use uuid::Uuid;
+}
@@ -0,0 +1,99 @@
+//! Matching of the event kinds against the patterns used by the listeners.
+//!
+//! The event kinds are split in segments by dots, like `user.created`.
+//! In a pattern, the segment `*` matches exactly one segment,
+//! and the segment `**` matches any number of segments, including none.
+
+const SEPARATOR: char = '.';
+const ONE_SEGMENT: &str = "*";
+const ANY_SEGMENTS: &str = "**";
+
+/// Checks if an event kind is a pattern, which is if it contains a wildcard segment.
+pub(crate) fn is_pattern(event_kind: &str) -> bool {
+    event_kind
+        .split(SEPARATOR)
+        .any(|segment| segment == ONE_SEGMENT || segment == ANY_SEGMENTS)
+}
+
+/// Returns the parent of an event kind in the hierarchy of the event kinds, if it's not a top-level kind.
+pub(crate) fn parent_kind(event_kind: &str) -> Option<&str> {
+    event_kind.rsplit_once(SEPARATOR).map(|(parent, _)| parent)
+}
+
+/// Checks if an event kind is matched by a pattern.
+pub(crate) fn matches(pattern: &str, event_kind: &str) -> bool {
+    let pattern = pattern.split(SEPARATOR).collect::<Vec<_>>();
+    let event_kind = event_kind.split(SEPARATOR).collect::<Vec<_>>();
+
+    matches_segments(&pattern, &event_kind)
+}
+
+fn matches_segments(pattern: &[&str], event_kind: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => event_kind.is_empty(),
+        Some((&ANY_SEGMENTS, rest)) => {
+            (0..=event_kind.len()).any(|skipped| matches_segments(rest, &event_kind[skipped..]))
+        }
+        Some((&segment, rest)) => match event_kind.split_first() {
+            Some((&kind_segment, kind_rest)) if segment == ONE_SEGMENT || segment == kind_segment => {
+                matches_segments(rest, kind_rest)
+            }
+            _ => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_pattern, matches, parent_kind};
+
+    #[test]
+    fn detects_the_wildcard_segments() {
+        assert!(is_pattern("user.*"));
+        assert!(is_pattern("**"));
+        assert!(is_pattern("user.**.failed"));
+        assert!(!is_pattern("user.created"));
+        assert!(!is_pattern("user*.created"));
+    }
+
+    #[test]
+    fn matches_the_exact_kind() {
+        assert!(matches("user.created", "user.created"));
+        assert!(!matches("user.created", "user.deleted"));
+        assert!(!matches("user.created", "user"));
+    }
+
+    #[test]
+    fn one_segment_wildcard_matches_exactly_one_segment() {
+        assert!(matches("user.*", "user.created"));
+        assert!(matches("*.created", "user.created"));
+        assert!(!matches("user.*", "user"));
+        assert!(!matches("user.*", "user.profile.updated"));
+        assert!(!matches("*", "user.created"));
+    }
+
+    #[test]
+    fn any_segments_wildcard_matches_any_number_of_segments() {
+        assert!(matches("user.**", "user.created"));
+        assert!(matches("user.**", "user.profile.updated"));
+        assert!(matches("user.**.failed", "user.login.failed"));
+        assert!(matches("user.**.failed", "user.login.retry.failed"));
+        assert!(!matches("user.**", "order.created"));
+        assert!(matches("**", "user.profile.updated"));
+    }
+
+    #[test]
+    fn any_segments_wildcard_matches_no_segment() {
+        assert!(matches("a.**", "a"));
+        assert!(matches("user.**.failed", "user.failed"));
+        assert!(matches("**", ""));
+        assert!(!matches("a.*", "a"));
+    }
+
+    #[test]
+    fn returns_the_parent_kind() {
+        assert_eq!(parent_kind("orders.item.added"), Some("orders.item"));
+        assert_eq!(parent_kind("orders.item"), Some("orders"));
+        assert_eq!(parent_kind("orders"), None);
+    }
+}
@@ -0,0 +1,398 @@
+//! The registry core shared by the event managers, whatever the type of the listeners they store.
+//!
+//! It indexes the listeners by event kind and by identifier, keeps them in invocation order,
+//! finds the listeners reached by an emission, and repairs itself after a panic poisoned its lock.
+
+use crate::{EventKey, HubHealth};
+use uuid::Uuid;
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+/// The priority of the listeners registered without an explicit priority.
+pub(crate) const DEFAULT_PRIORITY: i32 = 0;
+
+/// The listeners of each event kind, sorted in invocation order.
+pub(crate) type ListenerMap<L, K> = HashMap<K, Vec<ListenerEntry<L>>>;
+
+#[derive(Clone)]
+pub(crate) struct ListenerEntry<L> {
+    pub(crate) id:       Uuid,
+    pub(crate) priority: i32,
+    pub(crate) order:    u64,
+    pub(crate) listener: L,
+}
+
+/// A registry whose lock can be recovered after a panic poisoned it.
+pub(crate) trait Recoverable: Sized {
+    /// Restores the consistency of the registry,
+    /// which can be broken when a panic interrupts a modification of the registry.
+    ///
+    /// Returns the number of repaired entries.
+    fn repair(&mut self) -> usize;
+
+    /// Returns the report about the recoveries of the registry.
+    fn health_mut(&mut self) -> &mut HubHealth;
+
+    /// Locks the registry for reading, recovering it first if the lock is poisoned.
+    fn read_recovered(lock: &RwLock<Self>) -> RwLockReadGuard<'_, Self> {
+        if lock.is_poisoned() {
+            drop(Self::write_recovered(lock));
+        }
+
+        lock.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the registry for writing, recovering it first if the lock is poisoned.
+    fn write_recovered(lock: &RwLock<Self>) -> RwLockWriteGuard<'_, Self> {
+        lock.write().unwrap_or_else(|poisoned| {
+            let mut registry = poisoned.into_inner();
+            let repaired = registry.repair();
+            let health = registry.health_mut();
+
+            health.poison_recoveries += 1;
+            health.repaired_entries += repaired;
+            lock.clear_poison();
+
+            registry
+        })
+    }
+}
+
+/// The listeners of an event manager, indexed by event kind and by identifier.
+pub(crate) struct RegistryCore<L, K: EventKey> {
+    listeners:  ListenerMap<L, K>,
+    links:      HashMap<Uuid, K>,
+    patterns:   HashSet<K>,
+    next_order: u64,
+}
+
+impl<L, K: EventKey> RegistryCore<L, K> {
+    pub(crate) fn new() -> Self {
+        Self {
+            listeners:  HashMap::new(),
+            links:      HashMap::new(),
+            patterns:   HashSet::new(),
+            next_order: 0,
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.listeners.clear();
+        self.links.clear();
+        self.patterns.clear();
+    }
+
+    /// Restores the consistency between the listeners and their links,
+    /// which can be broken when a panic interrupts a modification of the registry.
+    ///
+    /// Returns the number of repaired entries.
+    pub(crate) fn repair(&mut self) -> usize {
+        let mut repaired = 0;
+
+        self.listeners.retain(|_, listeners| {
+            let keep = !listeners.is_empty();
+            if !keep {
+                repaired += 1;
+            }
+
+            keep
+        });
+
+        let listeners = &self.listeners;
+        self.links.retain(|listener_id, event_kind| {
+            let keep = listeners
+                .get(event_kind)
+                .is_some_and(|list| list.iter().any(|entry| entry.id == *listener_id));
+            if !keep {
+                repaired += 1;
+            }
+
+            keep
+        });
+
+        for (event_kind, listeners) in &mut self.listeners {
+            listeners.retain(|entry| match self.links.get(&entry.id) {
+                Some(kind) => kind == event_kind,
+                None => {
+                    self.links.insert(entry.id, event_kind.clone());
+                    repaired += 1;
+
+                    true
+                }
+            });
+        }
+
+        self.listeners.retain(|_, listeners| !listeners.is_empty());
+
+        let listeners = &self.listeners;
+        self.patterns.retain(|pattern| listeners.contains_key(pattern));
+        for event_kind in listeners.keys().filter(|event_kind| event_kind.is_pattern()) {
+            if self.patterns.insert(event_kind.clone()) {
+                repaired += 1;
+            }
+        }
+
+        repaired
+    }
+
+    pub(crate) fn listeners(&self) -> &ListenerMap<L, K> {
+        &self.listeners
+    }
+
+    pub(crate) fn listener(&self, listener_id: Uuid) -> Option<&L> {
+        let event_kind = self.links.get(&listener_id)?;
+
+        self.listeners
+            .get(event_kind)?
+            .iter()
+            .find(|entry| entry.id == listener_id)
+            .map(|entry| &entry.listener)
+    }
+
+    /// Returns the listeners reached by the emission of some event kinds, or of all the event kinds with `None`,
+    /// with the event kind or the pattern they are registered for.
+    ///
+    /// An event kind reaches the listeners registered for this exact kind and for the patterns matching it.
+    /// The listeners are sorted by decreasing priority, then in registration order, even across event kinds,
+    /// and a listener reached by several event kinds is listed once.
+    pub(crate) fn matching_listeners(&self, event_kinds: Option<&[K]>) -> Vec<(&K, &ListenerEntry<L>)> {
+        let registered_kinds = match event_kinds {
+            None => self.listeners.keys().collect::<Vec<_>>(),
+            Some(event_kinds) => {
+                let mut registered_kinds = Vec::new();
+                for event_kind in event_kinds {
+                    let exact = self
+                        .listeners
+                        .get_key_value(event_kind)
+                        .map(|(registered, _)| registered);
+                    let patterns = self
+                        .patterns
+                        .iter()
+                        .filter(|pattern| *pattern != event_kind && pattern.matches(event_kind));
+
+                    for registered in exact.into_iter().chain(patterns) {
+                        if !registered_kinds.contains(&registered) {
+                            registered_kinds.push(registered);
+                        }
+                    }
+                }
+
+                registered_kinds
+            }
+        };
+
+        let mut matching = registered_kinds
+            .into_iter()
+            .filter_map(|registered| self.listeners.get_key_value(registered))
+            .flat_map(|(registered, entries)| entries.iter().map(move |entry| (registered, entry)))
+            .collect::<Vec<_>>();
+
+        matching.sort_by_key(|(_, entry)| (Reverse(entry.priority), entry.order));
+
+        matching
+    }
+
+    /// Returns the listeners reached by the emission of some event kinds when the events bubble,
+    /// which are the listeners matching the event kinds, then the listeners matching their parents, level by level.
+    ///
+    /// A listener reached at several levels is listed once, at the first level reaching it.
+    pub(crate) fn bubbling_listeners(&self, event_kinds: &[K]) -> Vec<(&K, &ListenerEntry<L>)> {
+        let mut bubbling = self.matching_listeners(Some(event_kinds));
+        let mut reached = bubbling.iter().map(|(_, entry)| entry.id).collect::<HashSet<_>>();
+        let mut parents = event_kinds.iter().filter_map(K::parent).collect::<Vec<_>>();
+
+        while !parents.is_empty() {
+            for (registered, entry) in self.matching_listeners(Some(&parents)) {
+                if reached.insert(entry.id) {
+                    bubbling.push((registered, entry));
+                }
+            }
+
+            parents = parents.iter().filter_map(K::parent).collect();
+        }
+
+        bubbling
+    }
+
+    pub(crate) fn remove_listener(&mut self, listener_id: Uuid) -> bool {
+        if let Some(event_kind) = self.links.remove(&listener_id)
+            && let Some(listeners) = self.listeners.get_mut(&event_kind)
+        {
+            listeners.retain(|entry| entry.id != listener_id);
+            if listeners.is_empty() {
+                self.listeners.remove(&event_kind);
+                self.patterns.remove(&event_kind);
+            }
+
+            return true;
+        }
+
+        false
+    }
+
+    pub(crate) fn remove_listeners_by_kind(&mut self, event_kind: &K) -> usize {
+        match self.listeners.remove(event_kind) {
+            None => 0,
+            Some(listeners) => {
+                self.patterns.remove(event_kind);
+                for entry in &listeners {
+                    self.links.remove(&entry.id);
+                }
+
+                listeners.len()
+            }
+        }
+    }
+
+    /// Inserts a listener after the listeners with a higher or equal priority,
+    /// so the listeners with the same priority are called in registration order.
+    pub(crate) fn insert_listener(&mut self, listener_id: Uuid, event_kind: &K, priority: i32, listener: L) -> Uuid {
+        let event_kind = event_kind.clone();
+        if event_kind.is_pattern() {
+            self.patterns.insert(event_kind.clone());
+        }
+
+        let listeners = self.listeners.entry(event_kind.clone()).or_default();
+        let position = listeners.partition_point(|entry| entry.priority >= priority);
+
+        listeners.insert(
+            position,
+            ListenerEntry {
+                id: listener_id,
+                priority,
+                order: self.next_order,
+                listener,
+            },
+        );
+
+        self.next_order += 1;
+        self.links.insert(listener_id, event_kind);
+
+        listener_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Recoverable, RegistryCore, DEFAULT_PRIORITY};
+    use crate::HubHealth;
+    use uuid::Uuid;
+    use std::{
+        sync::{Arc, RwLock},
+        thread,
+    };
+
+    struct TestRegistry {
+        core:   RegistryCore<usize, String>,
+        health: HubHealth,
+    }
+
+    impl Recoverable for TestRegistry {
+        fn repair(&mut self) -> usize {
+            self.core.repair()
+        }
+
+        fn health_mut(&mut self) -> &mut HubHealth {
+            &mut self.health
+        }
+    }
+
+    fn kind(name: &str) -> String {
+        String::from(name)
+    }
+
+    #[test]
+    fn repair_relinks_the_listeners_without_link() {
+        let mut core = RegistryCore::new();
+        let listener_id = core.insert_listener(Uuid::new_v4(), &kind("user.created"), DEFAULT_PRIORITY, 1);
+
+        core.links.clear();
+
+        assert_eq!(core.repair(), 1);
+        assert_eq!(core.listener(listener_id), Some(&1));
+        assert!(core.remove_listener(listener_id));
+        assert!(core.listeners().is_empty());
+    }
+
+    #[test]
+    fn repair_drops_the_stale_links_and_the_empty_lists() {
+        let mut core = RegistryCore::<u32, String>::new();
+
+        core.links.insert(Uuid::new_v4(), kind("user.created"));
+        core.listeners.insert(kind("user.deleted"), vec![]);
+
+        assert_eq!(core.repair(), 2);
+        assert!(core.links.is_empty());
+        assert!(core.listeners().is_empty());
+    }
+
+    #[test]
+    fn repair_restores_the_missing_patterns() {
+        let mut core = RegistryCore::new();
+
+        core.insert_listener(Uuid::new_v4(), &kind("user.*"), DEFAULT_PRIORITY, 1);
+        core.patterns.clear();
+
+        assert_eq!(core.repair(), 1);
+        assert_eq!(core.matching_listeners(Some(&[kind("user.created")])).len(), 1);
+    }
+
+    #[test]
+    fn bubbling_listeners_are_listed_level_by_level() {
+        let mut core = RegistryCore::new();
+
+        for (value, event_kind) in ["orders", "orders.**", "orders.*", "orders.item", "orders.item.added"]
+            .into_iter()
+            .enumerate()
+        {
+            core.insert_listener(Uuid::new_v4(), &kind(event_kind), DEFAULT_PRIORITY, value);
+        }
+
+        let reached = core
+            .bubbling_listeners(&[kind("orders.item.added")])
+            .into_iter()
+            .map(|(registered, _)| registered.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            reached,
+            ["orders.**", "orders.item.added", "orders.*", "orders.item", "orders"]
+        );
+    }
+
+    #[test]
+    fn write_recovered_repairs_a_poisoned_registry() {
+        let mut registry = TestRegistry {
+            core:   RegistryCore::new(),
+            health: HubHealth::default(),
+        };
+
+        let listener_id = registry
+            .core
+            .insert_listener(Uuid::new_v4(), &kind("user.created"), DEFAULT_PRIORITY, 1);
+
+        let lock = Arc::new(RwLock::new(registry));
+        let poisoning = Arc::clone(&lock);
+        let poisoned = thread::spawn(move || {
+            let mut registry = poisoning.write().unwrap();
+
+            registry.core.links.clear();
+            panic!("Interrupted modification");
+        });
+
+        assert!(poisoned.join().is_err());
+        assert!(lock.is_poisoned());
+
+        let mut registry = TestRegistry::write_recovered(&lock);
+        assert_eq!(registry.health.poison_recoveries, 1);
+        assert_eq!(registry.health.repaired_entries, 1);
+        assert!(registry.core.remove_listener(listener_id));
+        drop(registry);
+
+        assert!(!lock.is_poisoned());
+        assert_eq!(TestRegistry::read_recovered(&lock).health.poison_recoveries, 1);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{EventKey, EventManager};
+use uuid::Uuid;
+
+type Remover = Box<dyn FnOnce(Uuid) + Send + Sync>;
+
+/// A guard that keeps a listener registered.
+///
+/// The listener is removed from its event manager when the subscription is dropped,
+/// unless the subscription is detached with [`Subscription::detach`].
+///
+/// # Example
+/// ```rust
+/// use emitix::{event_hub::EventHub, EventManager};
+///
+/// let manager = EventHub::default();
+/// let subscription = manager
+///     .add_scoped_listener("Events You Like", |event: String| {
+///         println!("Event received: {}", event);
+///     })
+///     .unwrap();
+///
+/// assert_eq!(manager.listeners_count("Events You Like").unwrap(), 1);
+///
+/// drop(subscription);
+/// assert_eq!(manager.listeners_count("Events You Like").unwrap(), 0);
+/// ```
+#[must_use = "the listener is removed as soon as the subscription is dropped"]
+pub struct Subscription {
+    listener_id: Uuid,
+    remover:     Option<Remover>,
+}
+
+impl Subscription {
+    pub(crate) fn new<F: FnOnce(Uuid) + Send + Sync + 'static>(listener_id: Uuid, remover: F) -> Self {
+        Self {
+            listener_id,
+            remover: Some(Box::new(remover)),
+        }
+    }
+
+    /// Creates a subscription removing the listener from the manager.
+    pub(crate) fn removed_from<T, K, M>(manager: M, listener_id: Uuid) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+        K: EventKey,
+        M: EventManager<T, K>, {
+        Self::new(listener_id, move |listener_id| {
+            // Nothing more can be done from a drop if the underlying data structure can't be accessed.
+            let _ = manager.remove_listener(listener_id);
+        })
+    }
+
+    /// Returns the identifier of the guarded listener.
+    pub fn listener_id(&self) -> Uuid {
+        self.listener_id
+    }
+
+    /// Releases the guard without removing the listener.
+    ///
+    /// # Returns
+    /// - `Uuid` which is the identifier to pass to `EventManager::remove_listener` for removing the listener.
+    pub fn detach(mut self) -> Uuid {
+        self.remover = None;
+        self.listener_id
+    }
+
+    /// Removes the listener now, it's the same as dropping the subscription.
+    pub fn unsubscribe(self) {}
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(remover) = self.remover.take() {
+            remover(self.listener_id);
+        }
+    }
+}
+
+/// A group of subscriptions, which are removed together.
+///
+/// All the listeners are removed from their event managers when the group is dropped,
+/// unless the group is detached with [`Subscriptions::detach`].
+///
+/// # Example
+/// ```rust
+/// use emitix::{event_hub::EventHub, EventManager, Subscriptions};
+///
+/// let manager = EventHub::default();
+/// let mut subscriptions = Subscriptions::new();
+///
+/// subscriptions.push(
+///     manager
+///         .add_scoped_listener("user.created", |name: String| {
+///             println!("User created: {name}")
+///         })
+///         .unwrap(),
+/// );
+/// subscriptions.push(
+///     manager
+///         .add_scoped_listener("user.deleted", |name: String| {
+///             println!("User deleted: {name}")
+///         })
+///         .unwrap(),
+/// );
+///
+/// assert_eq!(subscriptions.len(), 2);
+///
+/// drop(subscriptions);
+/// assert!(!manager.has_listeners("user.created").unwrap());
+/// assert!(!manager.has_listeners("user.deleted").unwrap());
+/// ```
+#[derive(Default)]
+#[must_use = "the listeners are removed as soon as the subscriptions are dropped"]
+pub struct Subscriptions {
+    subscriptions: Vec<Subscription>,
+}
+
+impl Subscriptions {
+    /// Creates an empty group of subscriptions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a subscription to the group.
+    ///
+    /// # Arguments
+    /// - `subscription`: The subscription whose listener is removed with the group.
+    pub fn push(&mut self, subscription: Subscription) {
+        self.subscriptions.push(subscription);
+    }
+
+    /// Returns the number of subscriptions in the group.
+    pub fn len(&self) -> usize {
+        self.subscriptions.len()
+    }
+
+    /// Checks if the group has no subscription.
+    pub fn is_empty(&self) -> bool {
+        self.subscriptions.is_empty()
+    }
+
+    /// Returns the identifiers of the guarded listeners.
+    pub fn listener_ids(&self) -> Vec<Uuid> {
+        self.subscriptions.iter().map(Subscription::listener_id).collect()
+    }
+
+    /// Releases the guards without removing the listeners.
+    ///
+    /// # Returns
+    /// - `Vec<Uuid>` which are the identifiers to pass to `EventManager::remove_listener` for removing the listeners.
+    pub fn detach(self) -> Vec<Uuid> {
+        self.subscriptions.into_iter().map(Subscription::detach).collect()
+    }
+
+    /// Removes the listeners now, it's the same as dropping the group.
+    pub fn unsubscribe(self) {}
+}
+
+impl Extend<Subscription> for Subscriptions {
+    fn extend<I: IntoIterator<Item = Subscription>>(&mut self, subscriptions: I) {
+        self.subscriptions.extend(subscriptions);
+    }
+}
+
+impl FromIterator<Subscription> for Subscriptions {
+    fn from_iter<I: IntoIterator<Item = Subscription>>(subscriptions: I) -> Self {
+        Self {
+            subscriptions: subscriptions.into_iter().collect(),
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use anyhow::Result;
+use crate::Result;
+use uuid::Uuid;
 
 pub trait EventEmitter<T: Clone + Send + Sync + 'static = ()>: Send + Sync + 'static {
     /// Emits an event of type `T` to all registered listeners for the specified event kind.
@@ -8,8 +9,8 @@ pub trait EventEmitter<T: Clone + Send + Sync + 'static = ()>: Send + Sync + 'st
     ///
     /// # Returns
     /// - `Ok(())` if the event was successfully emitted to all listeners.
-    /// - `Err(anyhow::Error)` if access to the underlying data structure fails,
-    ///     or if no listeners are found for the event kind.
+    /// - `Err(EmitixError::LockPoisoned)` if access to the underlying data structure fails.
+    /// - `Err(EmitixError::ListenerFailures)` if some listeners failed, with the identifier of each failing listener.
     fn emit(&self, event_arg: T) -> Result<()>;
 
     /// Returns the event emitter.
@@ -17,4 +18,12 @@ pub trait EventEmitter<T: Clone + Send + Sync + 'static = ()>: Send + Sync + 'st
     /// # Returns
     /// - A cloned instance of the event emitter.
     fn clone(&self) -> Box<dyn EventEmitter<T>>;
+
+    /// Returns the identifier of the emitter, shared by its clones.
+    ///
+    /// It's the source emitter identifier of the envelopes of the events emitted by the emitter.
+    /// By default, an emitter has no identifier.
+    fn id(&self) -> Option<Uuid> {
+        None
+    }
 }
@@ -1,46 +1,57 @@
 use super::EventEmitter;
-use anyhow::Result;
+use crate::{EventKey, Result, Subscription};
 use uuid::Uuid;
 
-pub trait EventManager<T: Clone + Send + Sync + 'static = ()>: Default + Clone + Send + Sync + 'static {
+pub trait EventManager<T: Clone + Send + Sync + 'static = (), K: EventKey = String>:
+    Default + Clone + Send + Sync + 'static {
     /// Lists all event kinds that have registered listeners.
     ///
+    /// The patterns are listed as they were registered, they are not expanded into event kinds.
+    ///
     /// # Returns
-    /// - `Ok(Vec<String>)` containing the names of all event kinds with listeners.
-    /// - `Err(anyhow::Error)` if access to the underlying data structure fails.
-    fn list_event_kinds(&self) -> Result<Vec<String>>;
+    /// - `Ok(Vec<K>)` containing the keys of all event kinds with listeners.
+    /// - `Err(EmitixError)` if access to the underlying data structure fails.
+    fn list_event_kinds(&self) -> Result<Vec<K>>;
 
     /// Checks if there are any listeners for a specific event kind.
     ///
+    /// The listeners registered for the patterns matching the event kind are taken into account.
+    ///
     /// # Arguments
-    /// - `event_kind`: A string that identifies the type of event to check for listeners.
+    /// - `event_kind`: A key that identifies the type of event to check for listeners.
     ///
     /// # Returns
     /// - `Ok(bool)` indicating whether there are listeners for the specified event kind.
-    /// - `Err(anyhow::Error)` if access to the underlying data structure fails.
-    fn has_listeners(&self, event_kind: &str) -> Result<bool>;
+    /// - `Err(EmitixError)` if access to the underlying data structure fails.
+    fn has_listeners<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q) -> Result<bool>;
 
     /// Returns the number of listeners for a specific event kind.
     ///
+    /// It's the number of listeners reached by an emission of the event kind,
+    /// including the listeners registered for the patterns matching it.
+    ///
     /// # Arguments
-    /// - `event_kind`: A string that identifies the type of event whose listeners count is requested.
+    /// - `event_kind`: A key that identifies the type of event whose listeners count is requested.
     ///
     /// # Returns
     /// - `Ok(usize)` representing the number of listeners for the specified event kind.
-    /// - `Err(anyhow::Error)` if access to the underlying data structure fails.
-    fn listeners_count(&self, event_kind: &str) -> Result<usize>;
+    /// - `Err(EmitixError)` if access to the underlying data structure fails.
+    fn listeners_count<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q) -> Result<usize>;
 
     /// Clears all listeners.
     ///
     /// # Returns
     /// - `Ok(())` if the listeners were successfully cleared.
-    /// - `Err(anyhow::Error)` if access to the underlying data structure fails.
+    /// - `Err(EmitixError)` if access to the underlying data structure fails.
     fn clear_listeners(&self) -> Result<()>;
 
     /// Adds a listener for a specific event kind.
     ///
+    /// The event kind can be a pattern, split in segments by dots, like `user.*` or `**.created`.
+    /// The segment `*` matches exactly one segment, and the segment `**` matches any number of segments.
+    ///
     /// # Arguments
-    /// - `event_kind`: A string that identifies the type of event to listen for.
+    /// - `event_kind`: A key that identifies the type of event to listen for.
     /// - `listener`: A function that will be called when the event occurs.
     ///
     /// # Listener Function
@@ -49,8 +60,145 @@ pub trait EventManager<T: Clone + Send + Sync + 'static = ()>: Default + Clone +
     ///
     /// # Returns
     /// - `Ok(Uuid)` which is a unique identifier for the listener.
-    /// - `Err(anyhow::Error)` if access to the underlying data structure fails.
-    fn add_listener<F: FnMut(T) + Send + Sync + 'static>(&self, event_kind: &str, listener: F) -> Result<Uuid>;
+    /// - `Err(EmitixError)` if access to the underlying data structure fails.
+    fn add_listener<Q: ?Sized + ToOwned<Owned = K>, F: FnMut(T) + Send + Sync + 'static>(
+        &self,
+        event_kind: &Q,
+        listener: F,
+    ) -> Result<Uuid>;
+
+    /// Adds a listener with a priority for a specific event kind.
+    ///
+    /// Listeners with a higher priority are called first, and listeners with the same priority
+    /// are called in registration order. The listeners added by `add_listener` have the priority `0`.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event to listen for.
+    /// - `priority`: The priority of the listener.
+    /// - `listener`: A function that will be called when the event occurs.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the listener.
+    /// - `Err(EmitixError)` if access to the underlying data structure fails.
+    fn add_listener_with_priority<Q: ?Sized + ToOwned<Owned = K>, F: FnMut(T) + Send + Sync + 'static>(
+        &self,
+        event_kind: &Q,
+        priority: i32,
+        listener: F,
+    ) -> Result<Uuid>;
+
+    /// Adds a listener which can fail for a specific event kind.
+    ///
+    /// The errors returned by the listener are reported by the emission,
+    /// as `EmitixError::ListenerFailures`, after the other listeners are called.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event to listen for.
+    /// - `listener`: A function that will be called when the event occurs.
+    ///
+    /// # Listener Function
+    /// The listener function must accept a single argument of type `T`, and return an `anyhow::Result<()>`.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the listener.
+    /// - `Err(EmitixError)` if access to the underlying data structure fails.
+    fn add_fallible_listener<
+        Q: ?Sized + ToOwned<Owned = K>,
+        F: FnMut(T) -> anyhow::Result<()> + Send + Sync + 'static,
+    >(
+        &self,
+        event_kind: &Q,
+        listener: F,
+    ) -> Result<Uuid>;
+
+    /// Adds a listener which is removed after being called a given number of times.
+    ///
+    /// The listener unregisters itself right after its last call,
+    /// and it's never called more than `max_calls` times, even by concurrent emissions.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event to listen for.
+    /// - `max_calls`: The number of calls after which the listener is removed, which can't be zero.
+    /// - `listener`: A function that will be called when the event occurs.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the listener.
+    /// - `Err(EmitixError::InvalidArgument)` if `max_calls` is zero, no listener is registered.
+    /// - `Err(EmitixError)` if access to the underlying data structure fails.
+    fn add_listener_limited<Q: ?Sized + ToOwned<Owned = K>, F: FnMut(T) + Send + Sync + 'static>(
+        &self,
+        event_kind: &Q,
+        max_calls: usize,
+        listener: F,
+    ) -> Result<Uuid>;
+
+    /// Adds a listener which is removed after its first call.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event to listen for.
+    /// - `listener`: A function that will be called when the event occurs.
+    ///
+    /// # Returns
+    /// - `Ok(Uuid)` which is a unique identifier for the listener.
+    /// - `Err(EmitixError)` if access to the underlying data structure fails.
+    fn once<Q: ?Sized + ToOwned<Owned = K>, F: FnOnce(T) + Send + Sync + 'static>(
+        &self,
+        event_kind: &Q,
+        listener: F,
+    ) -> Result<Uuid> {
+        let mut listener = Some(listener);
+
+        self.add_listener_limited(event_kind, 1, move |event_arg| {
+            if let Some(listener) = listener.take() {
+                listener(event_arg);
+            }
+        })
+    }
+
+    /// Adds a listener for a specific event kind, guarded by a subscription.
+    ///
+    /// The listener is removed when the returned subscription is dropped.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event to listen for.
+    /// - `listener`: A function that will be called when the event occurs.
+    ///
+    /// # Returns
+    /// - `Ok(Subscription)` which keeps the listener registered while it is alive.
+    /// - `Err(EmitixError)` if access to the underlying data structure fails.
+    fn add_scoped_listener<Q: ?Sized + ToOwned<Owned = K>, F: FnMut(T) + Send + Sync + 'static>(
+        &self,
+        event_kind: &Q,
+        listener: F,
+    ) -> Result<Subscription> {
+        let listener_id = self.add_listener(event_kind, listener)?;
+
+        Ok(Subscription::removed_from(self.clone(), listener_id))
+    }
+
+    /// Adds a listener which can fail for a specific event kind, guarded by a subscription.
+    ///
+    /// The listener is removed when the returned subscription is dropped.
+    ///
+    /// # Arguments
+    /// - `event_kind`: A key that identifies the type of event to listen for.
+    /// - `listener`: A function that will be called when the event occurs.
+    ///
+    /// # Returns
+    /// - `Ok(Subscription)` which keeps the listener registered while it is alive.
+    /// - `Err(EmitixError)` if access to the underlying data structure fails.
+    fn add_scoped_fallible_listener<
+        Q: ?Sized + ToOwned<Owned = K>,
+        F: FnMut(T) -> anyhow::Result<()> + Send + Sync + 'static,
+    >(
+        &self,
+        event_kind: &Q,
+        listener: F,
+    ) -> Result<Subscription> {
+        let listener_id = self.add_fallible_listener(event_kind, listener)?;
+
+        Ok(Subscription::removed_from(self.clone(), listener_id))
+    }
 
     /// Removes a listener.
     ///
@@ -59,38 +207,40 @@ pub trait EventManager<T: Clone + Send + Sync + 'static = ()>: Default + Clone +
     ///
     /// # Returns
     /// - `Ok(())` if the listener was successfully removed.
-    /// - `Err(anyhow::Error)` if access to the underlying data structure fails.
+    /// - `Err(EmitixError)` if access to the underlying data structure fails.
     fn remove_listener(&self, listener_id: Uuid) -> Result<bool>;
 
     /// Removes all listeners for a specific event kind.
     ///
+    /// Only the listeners registered for this exact event kind or pattern are removed.
+    ///
     /// # Arguments
-    /// - `event_kind`: A string that identifies the type of event whose listeners should be removed.
+    /// - `event_kind`: A key that identifies the type of event whose listeners should be removed.
     ///
     /// # Returns
     /// - `Ok(usize)` representing the number of listeners removed.
-    /// - `Err(anyhow::Error)` if access to the underlying data structure fails.
-    fn remove_listeners_by_kind(&self, event_kind: &str) -> Result<usize>;
+    /// - `Err(EmitixError)` if access to the underlying data structure fails.
+    fn remove_listeners_by_kind<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q) -> Result<usize>;
 
     /// Creates a new event emitter for the specified event kind.
     ///
     /// # Arguments
-    /// - `event_kind`: A string that identifies the type of event this emitter will handle.
+    /// - `event_kind`: A key that identifies the type of event this emitter will handle.
     ///
     /// # Returns
     /// - `Ok(Box<dyn EventEmitter<T>>)` which is a boxed event emitter that can emit events of type `T`.
-    /// - `Err(anyhow::Error)` if the emitter could not be created.
-    fn new_emitter(&self, event_kind: &str) -> Box<dyn EventEmitter<T>>;
+    /// - `Err(EmitixError)` if the emitter could not be created.
+    fn new_emitter<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kind: &Q) -> Box<dyn EventEmitter<T>>;
 
     /// Emits an event of type `T` to all registered listeners for the specified event kinds.
     ///
     /// # Arguments
-    /// - `event_kinds`: A slice of strings that identifies the types of events to emit.
+    /// - `event_kinds`: A slice of keys that identifies the types of events to emit.
     ///
     /// # Returns
     /// - `Ok(Box<dyn EventEmitter<T>>)` which is a boxed event emitter that can emit events of type `T`.
-    /// - `Err(anyhow::Error)` if the emitter could not be created.
-    fn new_broadcast_emitter(&self, event_kinds: &[&str]) -> Box<dyn EventEmitter<T>>;
+    /// - `Err(EmitixError)` if the emitter could not be created.
+    fn new_broadcast_emitter<Q: ?Sized + ToOwned<Owned = K>>(&self, event_kinds: &[&Q]) -> Box<dyn EventEmitter<T>>;
 
     /// Returns a null emitter used as default emitter.
     ///
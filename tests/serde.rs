@@ -0,0 +1,170 @@
+#![cfg(feature = "serde")]
+
+use anyhow::bail;
+use emitix::{
+    event_hub::{EventHub, PanicPolicy, ReplayWindow},
+    Envelope,
+    ErrorReport,
+    EventManager,
+    EventMetadata,
+    FailureReport,
+    HubHealth,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use std::{fmt::Debug, time::Duration};
+
+/// Serializes a value to JSON, checks it's deserialized back to the same value, and returns the JSON.
+fn round_trip<V: Serialize + DeserializeOwned + PartialEq + Debug>(value: &V) -> Value {
+    let json = serde_json::to_value(value).unwrap();
+
+    assert_eq!(&serde_json::from_value::<V>(json.clone()).unwrap(), value);
+    json
+}
+
+fn field_names(json: &Value) -> Vec<&str> {
+    let mut names = json.as_object().unwrap().keys().map(String::as_str).collect::<Vec<_>>();
+
+    names.sort_unstable();
+    names
+}
+
+fn error_report() -> ErrorReport {
+    let manager = EventHub::default();
+
+    manager
+        .add_fallible_listener("user.created", |name: String| {
+            if name.is_empty() {
+                bail!("Empty name");
+            }
+
+            Ok(())
+        })
+        .unwrap();
+
+    manager.emit("user.created", String::new()).unwrap_err().report()
+}
+
+#[test]
+fn envelope_round_trips() {
+    let envelope = Envelope::new(String::from("user.created"), String::from("Alice"));
+    let json = round_trip(&envelope);
+
+    assert_eq!(
+        field_names(&json),
+        vec![
+            "causation_id",
+            "correlation_id",
+            "id",
+            "kind",
+            "payload",
+            "seq",
+            "source_emitter_id",
+            "timestamp",
+        ]
+    );
+    assert_eq!(json["id"], json!(envelope.id.to_string()));
+    assert_eq!(json["correlation_id"], json!(envelope.id.to_string()));
+    assert_eq!(json["causation_id"], Value::Null);
+    assert_eq!(json["seq"], Value::Null);
+    assert_eq!(json["kind"], json!("user.created"));
+    assert_eq!(json["payload"], json!("Alice"));
+}
+
+#[test]
+fn event_metadata_round_trips() {
+    let metadata = EventMetadata {
+        seq: Some(7),
+        ..Envelope::new(String::from("user.created"), ()).metadata()
+    };
+    let json = round_trip(&metadata);
+
+    assert_eq!(
+        field_names(&json),
+        vec![
+            "causation_id",
+            "correlation_id",
+            "id",
+            "seq",
+            "source_emitter_id",
+            "timestamp",
+        ]
+    );
+    assert_eq!(json["id"], json!(metadata.id.to_string()));
+    assert_eq!(json["seq"], json!(7));
+}
+
+#[test]
+fn failure_report_round_trips() {
+    let report = error_report().failures.remove(0);
+    let json = round_trip(&report);
+
+    assert_eq!(field_names(&json), vec!["id", "kind", "message", "panicked"]);
+    assert_eq!(json["id"], json!(report.id.to_string()));
+    assert_eq!(json["kind"], json!("user.created"));
+    assert_eq!(json["message"], json!("Empty name"));
+    assert_eq!(json["panicked"], json!(false));
+}
+
+#[test]
+fn error_report_round_trips() {
+    let report = error_report();
+    let json = round_trip(&report);
+
+    assert_eq!(field_names(&json), vec!["failures", "message"]);
+    assert_eq!(json["message"], json!(report.message));
+    assert_eq!(json["failures"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        serde_json::from_value::<FailureReport>(json["failures"][0].clone()).unwrap(),
+        report.failures[0]
+    );
+}
+
+#[test]
+fn hub_health_round_trips() {
+    let health = HubHealth {
+        poison_recoveries: 2,
+        repaired_entries:  3,
+    };
+
+    assert_eq!(
+        round_trip(&health),
+        json!({
+            "poison_recoveries": 2,
+            "repaired_entries": 3,
+        })
+    );
+}
+
+#[test]
+fn panic_policy_round_trips_as_its_variant_name() {
+    for (policy, name) in [
+        (PanicPolicy::Propagate, "Propagate"),
+        (PanicPolicy::Catch, "Catch"),
+        (PanicPolicy::CatchAndRemove, "CatchAndRemove"),
+        (PanicPolicy::CatchAndQuarantine, "CatchAndQuarantine"),
+    ] {
+        assert_eq!(round_trip(&policy), json!(name));
+    }
+}
+
+#[test]
+fn replay_window_round_trips() {
+    assert_eq!(
+        round_trip(&ReplayWindow::last(10)),
+        json!({
+            "capacity": 10,
+            "max_age": null,
+        })
+    );
+    assert_eq!(
+        round_trip(&ReplayWindow::last(10).with_max_age(Duration::from_millis(1500))),
+        json!({
+            "capacity": 10,
+            "max_age": {
+                "secs": 1,
+                "nanos": 500_000_000,
+            },
+        })
+    );
+}